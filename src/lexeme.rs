@@ -1,17 +1,27 @@
 use std::fmt::Display;
 
-use kind::LexemeKind;
+use kind::{LexemeKind, LexemeKindSet};
+
+use crate::lexer::{
+    error::{LexError, LexErrorKind},
+    next_coord,
+};
 
 pub mod accepter;
+mod dedent;
+mod doc;
+mod escape;
+mod ident;
 pub mod kind;
+pub mod number;
 
 /// A lexeme in the source code.
 ///
 /// A lexeme is a sequence of characters in the source code that is matched by a regular expression.
 /// They are categorized by their type, which is represented by a [`LexemeState`]. The lexeme also
-/// contains the slice of the source code that it represents, the start and end indices of the slice,
-/// and the start and end coordinates of the slice. Those can be used as debugging information for
-/// the parser
+/// contains the slice of the source code that it represents, the start and end byte offsets of the
+/// slice, and the start and end coordinates of the slice. Those can be used as debugging
+/// information for the parser
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Lexeme<'src, const N: usize = 1> {
     pub kind: LexemeAmbiguity,
@@ -22,7 +32,41 @@ pub struct Lexeme<'src, const N: usize = 1> {
     pub end_coord: Coord,
 }
 
+impl Lexeme<'_> {
+    /// Builds an error of the given `kind` spanning the whole lexeme.
+    pub(crate) fn error(&self, kind: LexErrorKind) -> LexError {
+        LexError {
+            kind,
+            start: self.start,
+            end: self.end,
+            start_coord: self.start_coord,
+            end_coord: self.end_coord,
+        }
+    }
+
+    /// Builds an error of the given `kind` spanning from `start` to `end`, both relative to the
+    /// start of the lexeme.
+    pub(crate) fn error_at(&self, kind: LexErrorKind, start: usize, end: usize) -> LexError {
+        LexError {
+            kind,
+            start: self.start + start,
+            end: self.start + end,
+            start_coord: self.coord_at(start),
+            end_coord: self.coord_at(end),
+        }
+    }
+
+    /// The coordinate of the char at `offset`, relative to the start of the lexeme.
+    pub(crate) fn coord_at(&self, offset: usize) -> Coord {
+        self.slice[..offset]
+            .chars()
+            .fold(self.start_coord, next_coord)
+    }
+}
+
 /// The coordinates of a character in the source code.
+///
+/// Both start at 1 and the column counts chars, not bytes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, PartialOrd, Ord)]
 pub struct Coord {
     pub line: usize,
@@ -50,16 +94,18 @@ impl Display for Coord {
     }
 }
 
+/// The kind of a lexeme, which might be ambiguous when more than one kind accepts its slice.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LexemeAmbiguity {
-    Ambiguous(LexemeKind, LexemeKind),
+    /// Every kind accepting the slice, there are always at least two.
+    Ambiguous(LexemeKindSet),
     Unambiguous(LexemeKind),
 }
 
 impl LexemeAmbiguity {
-    pub fn ambiguous(self) -> Option<(LexemeKind, LexemeKind)> {
+    pub fn ambiguous(self) -> Option<LexemeKindSet> {
         match self {
-            LexemeAmbiguity::Ambiguous(a, b) => Some((a, b)),
+            LexemeAmbiguity::Ambiguous(kinds) => Some(kinds),
             _ => None,
         }
     }
@@ -71,9 +117,9 @@ impl LexemeAmbiguity {
         }
     }
 
-    pub fn ambiguous_unchecked(self) -> (LexemeKind, LexemeKind) {
+    pub fn ambiguous_unchecked(self) -> LexemeKindSet {
         match self {
-            LexemeAmbiguity::Ambiguous(a, b) => (a, b),
+            LexemeAmbiguity::Ambiguous(kinds) => kinds,
             _ => unreachable!(),
         }
     }
@@ -84,4 +130,70 @@ impl LexemeAmbiguity {
             _ => unreachable!(),
         }
     }
+
+    /// Every possible kind, a single one if unambiguous.
+    pub fn kinds(self) -> LexemeKindSet {
+        match self {
+            LexemeAmbiguity::Ambiguous(kinds) => kinds,
+            LexemeAmbiguity::Unambiguous(kind) => LexemeKindSet::from([kind]),
+        }
+    }
+
+    /// Checks if the kind is whitespace or a comment, which ambiguous kinds never are.
+    pub fn is_trivia(self) -> bool {
+        self.unambiguous().is_some_and(LexemeKind::is_trivia)
+    }
+
+    /// Checks if `kind` is one of the possible kinds.
+    pub fn contains(self, kind: LexemeKind) -> bool {
+        self.kinds().contains(kind)
+    }
+
+    /// Keeps only the possible kinds for which `predicate` holds.
+    ///
+    /// Returns `None` if no kind is left.
+    pub fn filter(self, predicate: impl FnMut(LexemeKind) -> bool) -> Option<Self> {
+        Self::try_from(self.kinds().filter(predicate)).ok()
+    }
+}
+
+impl TryFrom<LexemeKindSet> for LexemeAmbiguity {
+    type Error = LexemeKindSet;
+
+    /// Builds the ambiguity from the possible `kinds`, failing if there are none.
+    fn try_from(kinds: LexemeKindSet) -> Result<Self, Self::Error> {
+        match kinds.len() {
+            0 => Err(kinds),
+            1 => Ok(LexemeAmbiguity::Unambiguous(kinds.first().unwrap())),
+            _ => Ok(LexemeAmbiguity::Ambiguous(kinds)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_ambiguity() {
+        let kinds =
+            LexemeKindSet::from([LexemeKind::KwVal, LexemeKind::IdentVal, LexemeKind::LitAtom]);
+        let ambiguity = LexemeAmbiguity::try_from(kinds).unwrap();
+
+        assert!(ambiguity.contains(LexemeKind::LitAtom));
+        assert_eq!(ambiguity.kinds().len(), 3);
+        assert_eq!(
+            ambiguity.filter(|kind| !kind.is_keyword()),
+            Some(LexemeAmbiguity::Ambiguous(LexemeKindSet::from([
+                LexemeKind::IdentVal,
+                LexemeKind::LitAtom
+            ])))
+        );
+        assert_eq!(
+            ambiguity.filter(LexemeKind::is_keyword),
+            Some(LexemeAmbiguity::Unambiguous(LexemeKind::KwVal))
+        );
+        assert_eq!(ambiguity.filter(|_| false), None);
+        assert!(LexemeAmbiguity::try_from(LexemeKindSet::new()).is_err());
+    }
 }
@@ -24,7 +24,7 @@ pub trait Accepter {
 }
 
 /// The state of a lexeme with defines if the current lexeme accept the next character or if it is a valid lexeme.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LexemeAccepter {
     Kw(kw::KwAccepter),
     Ident(ident::IdentAccepter),
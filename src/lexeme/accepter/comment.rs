@@ -1,6 +1,6 @@
 use super::Accepter;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CommentAccepter {
     Line(LineCommentAccepter),
     Block(BlockCommentAccepter),
@@ -34,11 +34,21 @@ impl CommentAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Line comments starting with exactly `///` are outer doc comments and those starting with `//!`
+/// are inner doc comments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum LineCommentAccepter {
     #[default]
     Unset,
     FirstSlash,
+    /// `//` was just read
+    SecondSlash,
+    /// `///` was just read, a fourth `/` makes it a regular comment
+    ThirdSlash,
+    /// Inside an outer doc comment
+    DocOuter,
+    /// Inside an inner doc comment
+    DocInner,
     Acceptable,
 }
 
@@ -46,47 +56,116 @@ impl Accepter for LineCommentAccepter {
     type Accepter = Self;
 
     fn acceptable(&self) -> bool {
-        *self == Self::Acceptable
+        matches!(
+            self,
+            Self::SecondSlash
+                | Self::ThirdSlash
+                | Self::DocOuter
+                | Self::DocInner
+                | Self::Acceptable
+        )
     }
 
     fn accept(self, c: char) -> Option<Self> {
         match self {
             Self::Unset if c == '/' => Some(Self::FirstSlash),
-            Self::FirstSlash if c == '/' => Some(Self::Acceptable),
-            Self::Acceptable if c != '\n' => Some(Self::Acceptable),
+            Self::FirstSlash if c == '/' => Some(Self::SecondSlash),
+            _ if c == '\n' => None,
+            Self::SecondSlash if c == '/' => Some(Self::ThirdSlash),
+            Self::SecondSlash if c == '!' => Some(Self::DocInner),
+            Self::ThirdSlash if c == '/' => Some(Self::Acceptable),
+            Self::ThirdSlash | Self::DocOuter => Some(Self::DocOuter),
+            Self::DocInner => Some(Self::DocInner),
+            Self::SecondSlash | Self::Acceptable => Some(Self::Acceptable),
             _ => None,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+impl LineCommentAccepter {
+    /// Checks if the comment read so far is an outer doc comment.
+    pub fn is_doc_outer(self) -> bool {
+        matches!(self, Self::ThirdSlash | Self::DocOuter)
+    }
+
+    /// Checks if the comment read so far is an inner doc comment.
+    pub fn is_doc_inner(self) -> bool {
+        self == Self::DocInner
+    }
+}
+
+/// Block comments nest, so the accepter keeps the depth of the innermost open comment along with
+/// whether it is a doc comment, which starts with exactly `/**`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum BlockCommentAccepter {
     #[default]
     Unset,
     FirstSlash,
-    FirstStar,
-    WaitingEndStar,
-    WaitingEndSlash,
-    Acceptable,
+    /// `/*` was just read
+    Opened,
+    /// `/**` was just read, a third `*` or a `/` make it a regular comment
+    DocOpened,
+    /// Inside the comment at the given depth
+    Inside(u32, bool),
+    /// A `*` was just read inside the comment, so a `/` closes the innermost comment
+    Star(u32, bool),
+    /// A `/` was just read inside the comment, so a `*` opens a nested comment
+    Slash(u32, bool),
+    Acceptable(bool),
 }
 
 impl Accepter for BlockCommentAccepter {
     type Accepter = Self;
 
     fn acceptable(&self) -> bool {
-        *self == Self::Acceptable
+        matches!(self, Self::Acceptable(_))
     }
 
     fn accept(self, c: char) -> Option<Self> {
         match self {
             Self::Unset if c == '/' => Some(Self::FirstSlash),
-            Self::FirstSlash if c == '*' => Some(Self::FirstStar),
-            Self::FirstStar if c != '*' => Some(Self::FirstStar),
-            Self::FirstStar if c == '*' => Some(Self::WaitingEndStar),
-            Self::WaitingEndStar if c == '*' => Some(Self::WaitingEndStar),
-            Self::WaitingEndStar if c == '/' => Some(Self::Acceptable),
-            Self::WaitingEndStar if c != '*' => Some(Self::FirstStar),
+            Self::FirstSlash if c == '*' => Some(Self::Opened),
+            Self::Opened if c == '*' => Some(Self::DocOpened),
+            Self::Opened => Self::Inside(1, false).accept(c),
+            Self::DocOpened if c == '*' => Some(Self::Star(1, false)),
+            Self::DocOpened if c == '/' => Some(Self::Acceptable(false)),
+            Self::DocOpened => Self::Inside(1, true).accept(c),
+            Self::Slash(depth, doc) if c == '*' => Some(Self::Inside(depth + 1, doc)),
+            Self::Star(1, doc) if c == '/' => Some(Self::Acceptable(doc)),
+            Self::Star(depth, doc) if c == '/' => Some(Self::Inside(depth - 1, doc)),
+            Self::Inside(depth, doc) | Self::Star(depth, doc) if c == '*' => {
+                Some(Self::Star(depth, doc))
+            }
+            Self::Inside(depth, doc) | Self::Slash(depth, doc) if c == '/' => {
+                Some(Self::Slash(depth, doc))
+            }
+            Self::Inside(depth, doc) | Self::Star(depth, doc) | Self::Slash(depth, doc) => {
+                Some(Self::Inside(depth, doc))
+            }
             _ => None,
         }
     }
 }
+
+impl BlockCommentAccepter {
+    /// The number of comments open in the comment read so far, none if it isn't open yet or is
+    /// already closed.
+    pub fn depth(self) -> u32 {
+        match self {
+            Self::Inside(depth, _) | Self::Star(depth, _) | Self::Slash(depth, _) => depth,
+            Self::Opened | Self::DocOpened => 1,
+            _ => 0,
+        }
+    }
+
+    /// Checks if the comment read so far is a doc comment.
+    pub fn is_doc(self) -> bool {
+        matches!(
+            self,
+            Self::Inside(_, true)
+                | Self::Star(_, true)
+                | Self::Slash(_, true)
+                | Self::Acceptable(true)
+        )
+    }
+}
@@ -7,7 +7,7 @@ const CBRACK: char = ']';
 const OBRACE: char = '{';
 const CBRACE: char = '}';
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DelimAccepter {
     /// The lexeme to accept a `(` operator.
     OParen(SingleCharAccepter<OPAREN>),
@@ -1,6 +1,6 @@
 use super::Accepter;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 /// A generic state accepter for a single character operator.
 ///
 /// This state is used by lexemes made of a single character namely `OP`.
@@ -28,7 +28,7 @@ impl<const CH: char> Accepter for SingleCharAccepter<CH> {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 /// A generic state accepter for a double character operator.
 ///
 /// This state is used by lexemes made of a double character namely `OP1 OP2`.
@@ -58,7 +58,7 @@ impl<const CH1: char, const CH2: char> Accepter for DoubleCharAccepter<CH1, CH2>
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 /// A generic state accepter for a triple character operator.
 ///
 /// This state is used by lexemes made of a triple character namely `OP1 OP2 OP3`.
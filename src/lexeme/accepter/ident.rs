@@ -1,12 +1,16 @@
 use super::Accepter;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum IdentAccepter {
     /// The lexeme to accept a value identifier.
     /// Regex: [a-z][a-z0-9_]+
+    /// With `unicode-idents` it also starts with a non uppercase XID_Start and continues with a non
+    /// uppercase XID_Continue
     Val(ValAccepter),
     /// The lexeme to accept a type identifier.
     /// Regex: [A-Z][a-zA-Z0-9]+
+    /// With `unicode-idents` it also starts with an uppercase XID_Start and continues with
+    /// XID_Continue
     Type(TypeAccepter),
     /// The lexeme to accept a tag identifier.
     /// Regex: #[a-z][a-z0-9]*(-[a-z0-9])*
@@ -56,7 +60,7 @@ impl IdentAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum ValAccepter {
     #[default]
     /// The lexeme hasn't started yet
@@ -74,8 +78,15 @@ impl Accepter for ValAccepter {
 
     fn accept(self, c: char) -> Option<Self> {
         match self {
-            Self::Unset if c.is_ascii_lowercase() => Some(Self::Acceptable),
-            Self::Acceptable if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' => {
+            Self::Unset if c.is_ascii_lowercase() || is_unicode_start(c, false) => {
+                Some(Self::Acceptable)
+            }
+            Self::Acceptable
+                if c.is_ascii_lowercase()
+                    || c.is_ascii_digit()
+                    || c == '_'
+                    || is_unicode_continue(c, false) =>
+            {
                 Some(Self::Acceptable)
             }
             _ => None,
@@ -83,7 +94,7 @@ impl Accepter for ValAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum TypeAccepter {
     #[default]
     /// The lexeme hasn't started yet
@@ -101,14 +112,42 @@ impl Accepter for TypeAccepter {
 
     fn accept(self, c: char) -> Option<Self> {
         match self {
-            Self::Unset if c.is_ascii_uppercase() => Some(Self::Acceptable),
-            Self::Acceptable if c.is_ascii_alphanumeric() => Some(Self::Acceptable),
+            Self::Unset if c.is_ascii_uppercase() || is_unicode_start(c, true) => {
+                Some(Self::Acceptable)
+            }
+            Self::Acceptable if c.is_ascii_alphanumeric() || is_unicode_continue(c, true) => {
+                Some(Self::Acceptable)
+            }
             _ => None,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Whether `c` is a non-ASCII XID_Start char starting a type identifier if `uppercase` or a value
+/// identifier otherwise. Chars without case start value identifiers.
+#[cfg(feature = "unicode-idents")]
+fn is_unicode_start(c: char, uppercase: bool) -> bool {
+    !c.is_ascii() && unicode_xid::UnicodeXID::is_xid_start(c) && c.is_uppercase() == uppercase
+}
+
+#[cfg(not(feature = "unicode-idents"))]
+fn is_unicode_start(_: char, _: bool) -> bool {
+    false
+}
+
+/// Whether `c` is a non-ASCII XID_Continue char continuing a type identifier if `uppercase` or a
+/// value identifier otherwise. Value identifiers don't continue with uppercase chars, as in ASCII.
+#[cfg(feature = "unicode-idents")]
+fn is_unicode_continue(c: char, uppercase: bool) -> bool {
+    !c.is_ascii() && unicode_xid::UnicodeXID::is_xid_continue(c) && (uppercase || !c.is_uppercase())
+}
+
+#[cfg(not(feature = "unicode-idents"))]
+fn is_unicode_continue(_: char, _: bool) -> bool {
+    false
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum TagAccepter {
     #[default]
     /// The lexeme hasn't started yet
@@ -139,7 +178,7 @@ impl Accepter for TagAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum MacroAccepter {
     #[default]
     /// The lexeme hasn't started yet
@@ -174,7 +213,7 @@ impl Accepter for MacroAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum SubtypeAccepter {
     #[default]
     /// The lexeme hasn't started yet
@@ -1,6 +1,6 @@
 use super::Accepter;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum KwAccepter {
     Val(ValAccepter),
     Fn(FnAccepter),
@@ -57,7 +57,7 @@ impl KwAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum ValAccepter {
     #[default]
     Unset,
@@ -83,7 +83,7 @@ impl Accepter for ValAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum FnAccepter {
     #[default]
     Unset,
@@ -107,7 +107,7 @@ impl Accepter for FnAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum TypeAccepter {
     #[default]
     Unset,
@@ -135,7 +135,7 @@ impl Accepter for TypeAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum TagAccepter {
     #[default]
     Unset,
@@ -161,7 +161,7 @@ impl Accepter for TagAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum MainAccepter {
     #[default]
     Unset,
@@ -189,7 +189,7 @@ impl Accepter for MainAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum MacroAccepter {
     #[default]
     Unset,
@@ -219,7 +219,7 @@ impl Accepter for MacroAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum ImportAccepter {
     #[default]
     Unset,
@@ -251,7 +251,7 @@ impl Accepter for ImportAccepter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum ObjectAccepter {
     #[default]
     Unset,
@@ -1,6 +1,6 @@
 use super::Accepter;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LitAccepter {
     /// The lexeme to accept a `int` literal.
     /// Regex: (0|[1-9][0-9_]*)(U|I)(8|16|32|64)?"
@@ -11,6 +11,10 @@ pub enum LitAccepter {
     Flt(FltAccepter),
     Chr(CharAccepter),
     Str(StrAccepter),
+    /// Only a candidate where a string resumes after an interpolation hole
+    StrCont(StrContAccepter),
+    StrRaw(RawStrAccepter),
+    StrMulti(MultiStrAccepter),
     Atom(AtomAccepter),
 }
 
@@ -26,6 +30,9 @@ impl Accepter for LitAccepter {
             Self::Flt(acp) => acp.acceptable(),
             Self::Chr(acp) => acp.acceptable(),
             Self::Str(acp) => acp.acceptable(),
+            Self::StrCont(acp) => acp.acceptable(),
+            Self::StrRaw(acp) => acp.acceptable(),
+            Self::StrMulti(acp) => acp.acceptable(),
             Self::Atom(acp) => acp.acceptable(),
         }
     }
@@ -39,6 +46,9 @@ impl Accepter for LitAccepter {
             Self::Flt(acp) => acp.accept(c).map(Self::Flt),
             Self::Chr(acp) => acp.accept(c).map(Self::Chr),
             Self::Str(acp) => acp.accept(c).map(Self::Str),
+            Self::StrCont(acp) => acp.accept(c).map(Self::StrCont),
+            Self::StrRaw(acp) => acp.accept(c).map(Self::StrRaw),
+            Self::StrMulti(acp) => acp.accept(c).map(Self::StrMulti),
             Self::Atom(acp) => acp.accept(c).map(Self::Atom),
         }
     }
@@ -55,12 +65,14 @@ impl LitAccepter {
             Flt(FltAccepter::default()),
             Chr(CharAccepter::default()),
             Str(StrAccepter::default()),
+            StrRaw(RawStrAccepter::default()),
+            StrMulti(MultiStrAccepter::default()),
             Atom(AtomAccepter::default()),
         ]
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum IntDecAccepter {
     #[default]
     /// The lexeme hasn't started yet
@@ -84,15 +96,11 @@ impl Accepter for IntDecAccepter {
     type Accepter = Self;
 
     fn acceptable(&self) -> bool {
-        matches!(
-            self,
-            Self::LeadingZero
-                | Self::LeadingNonZero
-                | Self::WithBitWidth(8)
-                | Self::WithBitWidth(16)
-                | Self::WithBitWidth(32)
-                | Self::WithBitWidth(64)
-        )
+        match self {
+            Self::LeadingZero | Self::LeadingNonZero => true,
+            Self::WithBitWidth(width) => is_bit_width(*width),
+            _ => false,
+        }
     }
 
     fn accept(self, c: char) -> Option<Self> {
@@ -104,19 +112,33 @@ impl Accepter for IntDecAccepter {
             Self::LeadingNonZero if c == '_' => Some(Self::LeadingNonZeroUnderscore),
             Self::LeadingNonZero if c == 'U' || c == 'I' => Some(Self::WithBitWidth(0)),
             Self::LeadingNonZeroUnderscore if c.is_ascii_digit() => Some(Self::LeadingNonZero),
-            Self::WithBitWidth(0) if c == '8' => Some(Self::WithBitWidth(8)),
-            Self::WithBitWidth(0) if c == '1' => Some(Self::WithBitWidth(1)),
-            Self::WithBitWidth(0) if c == '3' => Some(Self::WithBitWidth(3)),
-            Self::WithBitWidth(0) if c == '6' => Some(Self::WithBitWidth(6)),
-            Self::WithBitWidth(1) if c == '6' => Some(Self::WithBitWidth(16)),
-            Self::WithBitWidth(3) if c == '2' => Some(Self::WithBitWidth(32)),
-            Self::WithBitWidth(6) if c == '4' => Some(Self::WithBitWidth(64)),
+            Self::WithBitWidth(width) => accept_bit_width(width, c).map(Self::WithBitWidth),
             _ => None,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Reads the next char of the `8`, `16`, `32` or `64` width that follows the `U` or `I` of an
+/// integer literal suffix, given the `width` read so far (`0` if none, or its first digit).
+fn accept_bit_width(width: u8, c: char) -> Option<u8> {
+    match (width, c) {
+        (0, '8') => Some(8),
+        (0, '1') => Some(1),
+        (0, '3') => Some(3),
+        (0, '6') => Some(6),
+        (1, '6') => Some(16),
+        (3, '2') => Some(32),
+        (6, '4') => Some(64),
+        _ => None,
+    }
+}
+
+/// Checks if the `width` read by [`accept_bit_width`] is complete.
+fn is_bit_width(width: u8) -> bool {
+    matches!(width, 8 | 16 | 32 | 64)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum IntBinAccepter {
     #[default]
     /// The lexeme hasn't started yet
@@ -131,14 +153,23 @@ pub enum IntBinAccepter {
     /// Next can be either `0` or `1` or end
     TrailingUnderscore,
     /// The lexeme is a valid binary literal
+    /// Next can be either `0`, `1`, `_`, `U`, `I` or end
     Valid,
+    /// The lexeme has binary digits and `U` or `I` was read
+    /// Next can be either `8`, `1`, `3`, `6` or end
+    /// Then respectively end, `6`, `2` and `4`
+    WithBitWidth(u8),
 }
 
 impl Accepter for IntBinAccepter {
     type Accepter = Self;
 
     fn acceptable(&self) -> bool {
-        *self == Self::Valid
+        match self {
+            Self::Valid => true,
+            Self::WithBitWidth(width) => is_bit_width(*width),
+            _ => false,
+        }
     }
 
     fn accept(self, c: char) -> Option<Self> {
@@ -148,12 +179,14 @@ impl Accepter for IntBinAccepter {
             Self::Leading0b | Self::Valid if c == '0' || c == '1' => Some(Self::Valid),
             Self::Valid if c == '_' => Some(Self::TrailingUnderscore),
             Self::TrailingUnderscore if c == '0' || c == '1' => Some(Self::Valid),
+            Self::Valid if c == 'U' || c == 'I' => Some(Self::WithBitWidth(0)),
+            Self::WithBitWidth(width) => accept_bit_width(width, c).map(Self::WithBitWidth),
             _ => None,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum IntOctAccepter {
     #[default]
     /// The lexeme hasn't started yet
@@ -168,14 +201,23 @@ pub enum IntOctAccepter {
     /// Next can be either `0-7` or end
     TrailingUnderscore,
     /// The lexeme is a valid octal literal
+    /// Next can be either `0-7`, `_`, `U`, `I` or end
     Valid,
+    /// The lexeme has octal digits and `U` or `I` was read
+    /// Next can be either `8`, `1`, `3`, `6` or end
+    /// Then respectively end, `6`, `2` and `4`
+    WithBitWidth(u8),
 }
 
 impl Accepter for IntOctAccepter {
     type Accepter = Self;
 
     fn acceptable(&self) -> bool {
-        *self == Self::Valid
+        match self {
+            Self::Valid => true,
+            Self::WithBitWidth(width) => is_bit_width(*width),
+            _ => false,
+        }
     }
 
     fn accept(self, c: char) -> Option<Self> {
@@ -185,12 +227,14 @@ impl Accepter for IntOctAccepter {
             Self::Leading0o | Self::Valid if c.is_digit(8) => Some(Self::Valid),
             Self::Valid if c == '_' => Some(Self::TrailingUnderscore),
             Self::TrailingUnderscore if c.is_digit(8) => Some(Self::Valid),
+            Self::Valid if c == 'U' || c == 'I' => Some(Self::WithBitWidth(0)),
+            Self::WithBitWidth(width) => accept_bit_width(width, c).map(Self::WithBitWidth),
             _ => None,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum IntHexAccepter {
     #[default]
     /// The lexeme hasn't started yet
@@ -205,85 +249,146 @@ pub enum IntHexAccepter {
     /// Next can be either `0-9`, `a-f`, `A-F` or end
     TrailingUnderscore,
     /// The lexeme is a valid hexadecimal literal
+    /// Next can be either `0-9`, `a-f`, `A-F`, `_`, `U`, `I` or end
     Acceptable,
+    /// The lexeme has hex digits and `U` or `I` was read
+    /// Since those aren't hex digits, the digits that follow are always the width
+    /// Next can be either `8`, `1`, `3`, `6` or end
+    /// Then respectively end, `6`, `2` and `4`
+    WithBitWidth(u8),
 }
 
 impl Accepter for IntHexAccepter {
     type Accepter = Self;
 
     fn acceptable(&self) -> bool {
-        *self == Self::Acceptable
+        match self {
+            Self::Acceptable => true,
+            Self::WithBitWidth(width) => is_bit_width(*width),
+            _ => false,
+        }
     }
 
     fn accept(self, c: char) -> Option<Self> {
         match self {
             Self::Unset if c == '0' => Some(Self::LeadingZero),
             Self::LeadingZero if c == 'x' => Some(Self::Leading0x),
-            Self::Leading0x | Self::Acceptable
-                if c.is_ascii_hexdigit() || c.is_ascii_lowercase() || c.is_ascii_uppercase() =>
-            {
-                Some(Self::Acceptable)
-            }
+            Self::Leading0x | Self::Acceptable if c.is_ascii_hexdigit() => Some(Self::Acceptable),
             Self::Acceptable if c == '_' => Some(Self::TrailingUnderscore),
-            Self::TrailingUnderscore
-                if c.is_ascii_hexdigit() || c.is_ascii_lowercase() || c.is_ascii_uppercase() =>
-            {
-                Some(Self::Acceptable)
-            }
+            Self::TrailingUnderscore if c.is_ascii_hexdigit() => Some(Self::Acceptable),
+            Self::Acceptable if c == 'U' || c == 'I' => Some(Self::WithBitWidth(0)),
+            Self::WithBitWidth(width) => accept_bit_width(width, c).map(Self::WithBitWidth),
             _ => None,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum FltAccepter {
     #[default]
     /// The lexeme hasn't started yet
     Unset,
     /// The lexeme has a leading `0`
-    /// Next can only be either `.` or end
+    /// Next can be either `.`, `e`, `E` or `F`
     LeadingZero,
     /// The lexeme has a leading non-zero
-    /// Next can be either `0-9` or `.`
+    /// Next can be either `0-9`, `_`, `.`, `e`, `E` or `F`
     LeadingNonZero,
+    /// The lexeme has a leading non-zero and `_` was just read
+    /// Next can only be `0-9`
+    LeadingNonZeroUnderscore,
     /// The `.` was just read
     /// Next can only be `0-9`
     DecimalPoint,
-    /// The lexeme is a valid floating point literal
-    /// Next can be either `0-9` or end
-    Acceptable,
+    /// The lexeme has a fractional part
+    /// Next can be either `0-9`, `_`, `e`, `E`, `F` or end
+    Fraction,
+    /// The lexeme has a fractional part and `_` was just read
+    /// Next can only be `0-9`
+    FractionUnderscore,
+    /// The `e` or `E` was just read
+    /// Next can be either `0-9`, `+` or `-`
+    Exponent,
+    /// The sign of the exponent was just read
+    /// Next can only be `0-9`
+    ExponentSign,
+    /// The lexeme has an exponent
+    /// Next can be either `0-9`, `_`, `F` or end
+    ExponentDigits,
+    /// The lexeme has an exponent and `_` was just read
+    /// Next can only be `0-9`
+    ExponentUnderscore,
+    /// The `F` was just read
+    /// Next can be either `3` or `6`
+    /// Then respectively `2` and `4`
+    WithBitWidth(u8),
 }
 
 impl Accepter for FltAccepter {
     type Accepter = Self;
 
     fn acceptable(&self) -> bool {
-        *self == Self::Acceptable
+        matches!(
+            self,
+            Self::Fraction | Self::ExponentDigits | Self::WithBitWidth(32) | Self::WithBitWidth(64)
+        )
     }
 
     fn accept(self, c: char) -> Option<Self> {
         match self {
             Self::Unset if c == '0' => Some(Self::LeadingZero),
             Self::Unset if c.is_ascii_digit() && c != '0' => Some(Self::LeadingNonZero),
-            Self::LeadingZero if c == '.' => Some(Self::DecimalPoint),
             Self::LeadingNonZero if c.is_ascii_digit() => Some(Self::LeadingNonZero),
-            Self::LeadingNonZero if c == '.' => Some(Self::DecimalPoint),
-            Self::DecimalPoint if c.is_ascii_digit() => Some(Self::Acceptable),
-            Self::Acceptable if c.is_ascii_digit() => Some(Self::Acceptable),
+            Self::LeadingNonZero if c == '_' => Some(Self::LeadingNonZeroUnderscore),
+            Self::LeadingNonZeroUnderscore if c.is_ascii_digit() => Some(Self::LeadingNonZero),
+            Self::LeadingZero | Self::LeadingNonZero if c == '.' => Some(Self::DecimalPoint),
+            Self::DecimalPoint if c.is_ascii_digit() => Some(Self::Fraction),
+            Self::Fraction if c.is_ascii_digit() => Some(Self::Fraction),
+            Self::Fraction if c == '_' => Some(Self::FractionUnderscore),
+            Self::FractionUnderscore if c.is_ascii_digit() => Some(Self::Fraction),
+            Self::LeadingZero | Self::LeadingNonZero | Self::Fraction if c == 'e' || c == 'E' => {
+                Some(Self::Exponent)
+            }
+            Self::Exponent if c == '+' || c == '-' => Some(Self::ExponentSign),
+            Self::Exponent | Self::ExponentSign if c.is_ascii_digit() => Some(Self::ExponentDigits),
+            Self::ExponentDigits if c.is_ascii_digit() => Some(Self::ExponentDigits),
+            Self::ExponentDigits if c == '_' => Some(Self::ExponentUnderscore),
+            Self::ExponentUnderscore if c.is_ascii_digit() => Some(Self::ExponentDigits),
+            Self::LeadingZero | Self::LeadingNonZero | Self::Fraction | Self::ExponentDigits
+                if c == 'F' =>
+            {
+                Some(Self::WithBitWidth(0))
+            }
+            Self::WithBitWidth(0) if c == '3' => Some(Self::WithBitWidth(3)),
+            Self::WithBitWidth(0) if c == '6' => Some(Self::WithBitWidth(6)),
+            Self::WithBitWidth(3) if c == '2' => Some(Self::WithBitWidth(32)),
+            Self::WithBitWidth(6) if c == '4' => Some(Self::WithBitWidth(64)),
             _ => None,
         }
     }
 }
 
-// TODO: Implement escape sequences and symbols
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+// TODO: Implement symbols
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum CharAccepter {
     #[default]
     /// The lexeme hasn't started yet
     Unset,
     /// The lexeme has a leading `'`
-    /// Next can be either `a-z`, `A-Z`, `0-9`, `!`, `@`, `#`, `$`, `%`, `^`, `&`, `*`, `(`, `)`, `-`, `=`, `+`, `[`, `]`, `{`, `}`, `;`, `:`, `'`, `"`, `,`, `.`, `<`, `>`, `/`, `?`, `\`, `|`, `~`, ` ` or end
+    /// Next can be `\\` or any char but `'` or a line break
     LeadingSingleQuote,
+    /// A `\` was just read
+    /// Next can be `x`, `u` or the escaped char
+    Escape,
+    /// A `\x` escape with the given number of hex digits read
+    /// Next must be `0-9`, `a-f` or `A-F` until there are 2 digits
+    EscapeHex(u8),
+    /// A `\u` was just read
+    /// Next must be `{`
+    EscapeUnicodeOpen,
+    /// A `\u{` escape with the given number of hex digits read
+    /// Next can be `0-9`, `a-f` or `A-F` up to 6 digits or `}` after at least one
+    EscapeUnicode(u8),
     /// The lexeme has a leading `'` and a character was just read
     /// Next can be either `'` or end
     SingleChar,
@@ -301,54 +406,209 @@ impl Accepter for CharAccepter {
     fn accept(self, c: char) -> Option<Self> {
         match self {
             Self::Unset if c == '\'' => Some(Self::LeadingSingleQuote),
-            Self::LeadingSingleQuote
-                if c.is_ascii_alphanumeric() || c.is_ascii_punctuation() || c == ' ' =>
-            {
+            Self::LeadingSingleQuote if c == '\\' => Some(Self::Escape),
+            Self::LeadingSingleQuote if c != '\'' && c != '\n' && c != '\r' => {
                 Some(Self::SingleChar)
             }
+            Self::Escape if c == 'x' => Some(Self::EscapeHex(0)),
+            Self::Escape if c == 'u' => Some(Self::EscapeUnicodeOpen),
+            Self::Escape if c != '\n' => Some(Self::SingleChar),
+            Self::EscapeHex(0) if c.is_ascii_hexdigit() => Some(Self::EscapeHex(1)),
+            Self::EscapeHex(1) if c.is_ascii_hexdigit() => Some(Self::SingleChar),
+            Self::EscapeUnicodeOpen if c == '{' => Some(Self::EscapeUnicode(0)),
+            Self::EscapeUnicode(n) if n < 6 && c.is_ascii_hexdigit() => {
+                Some(Self::EscapeUnicode(n + 1))
+            }
+            Self::EscapeUnicode(n) if n > 0 && c == '}' => Some(Self::SingleChar),
             Self::SingleChar if c == '\'' => Some(Self::Acceptable),
             _ => None,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// A `{` opens an interpolation hole, ending the string fragment read so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum StrAccepter {
     #[default]
     /// The lexeme hasn't started yet
     Unset,
     /// The lexeme has a leading `"`
-    /// Next can be either `a-z`, `A-Z`, `0-9`, `!`, `@`, `#`, `$`, `%`, `^`, `&`, `*`, `(`, `)`, `-`, `=`, `+`, `[`, `]`, `{`, `}`, `;`, `:`, `'`, `"`, `,`, `.`, `<`, `>`, `/`, `?`, `\`, `|`, `~`, ` ` or end
+    /// Next can be any char
     LeadingDoubleQuote,
     /// The lexeme has a leading `"` and a character was just read
-    /// Next can be either `"` or end
+    /// Next can be any char
     Any,
     /// A `\` was just read
     /// Next will be escaped
     EscapeNext,
+    /// A `\u` was just read
+    /// Next can be `{`, which doesn't open a hole
+    EscapeUnicodeOpen,
+    /// A `\u{` was read
+    /// Next can be any char until `}`
+    EscapeUnicode,
     /// The lexeme has a trailing '"'"
     Acceptable,
+    /// The lexeme has a trailing `{` opening an interpolation hole
+    HoleStart,
 }
 
 impl StrAccepter {
     pub fn acceptable(self) -> bool {
-        self == Self::Acceptable
+        self == Self::Acceptable || self == Self::HoleStart
     }
 
     pub fn accept(self, c: char) -> Option<Self> {
         match self {
             Self::Unset if c == '"' => Some(Self::LeadingDoubleQuote),
-            Self::LeadingDoubleQuote if c != '\\' => Some(Self::Any),
-            acp if c == '\\' && acp != Self::EscapeNext => Some(Self::EscapeNext),
-            Self::Any if c != '"' => Some(Self::Any),
-            Self::Any if c == '"' => Some(Self::Acceptable),
+            Self::LeadingDoubleQuote | Self::Any if c == '"' => Some(Self::Acceptable),
+            Self::LeadingDoubleQuote | Self::Any if c == '{' => Some(Self::HoleStart),
+            Self::LeadingDoubleQuote | Self::Any if c == '\\' => Some(Self::EscapeNext),
+            Self::LeadingDoubleQuote | Self::Any => Some(Self::Any),
+            Self::EscapeNext if c == 'u' => Some(Self::EscapeUnicodeOpen),
             Self::EscapeNext => Some(Self::Any),
+            Self::EscapeUnicodeOpen if c == '{' => Some(Self::EscapeUnicode),
+            Self::EscapeUnicodeOpen => Self::Any.accept(c),
+            Self::EscapeUnicode if c == '"' => Some(Self::Acceptable),
+            Self::EscapeUnicode if c == '}' => Some(Self::Any),
+            Self::EscapeUnicode => Some(Self::EscapeUnicode),
+            _ => None,
+        }
+    }
+}
+
+/// The rest of a string after an interpolation hole, which starts with the `}` closing the hole
+/// and goes on just like a string after its leading `"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StrContAccepter {
+    #[default]
+    /// The lexeme hasn't started yet
+    /// Next must be `}`
+    HoleEnd,
+    /// The `}` was read
+    Resumed(StrAccepter),
+}
+
+impl Accepter for StrContAccepter {
+    type Accepter = Self;
+
+    fn acceptable(&self) -> bool {
+        matches!(self, Self::Resumed(acp) if acp.acceptable())
+    }
+
+    fn accept(self, c: char) -> Option<Self> {
+        match self {
+            Self::HoleEnd if c == '}' => Some(Self::Resumed(StrAccepter::LeadingDoubleQuote)),
+            Self::Resumed(acp) => acp.accept(c).map(Self::Resumed),
+            _ => None,
+        }
+    }
+}
+
+/// Multi-line strings are delimited by `"""`, so they can have up to two `"` in a row unescaped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MultiStrAccepter {
+    #[default]
+    /// The lexeme hasn't started yet
+    Unset,
+    /// The given number of leading `"` were read
+    /// Next must be `"` until there are 3
+    Opening(u8),
+    /// Inside the string
+    /// Next can be any char
+    Any,
+    /// A `\` was just read
+    /// Next will be escaped
+    EscapeNext,
+    /// The given number of `"` were read inside the string
+    /// Next can be any char, a third `"` closes the string
+    Closing(u8),
+    /// The lexeme has the trailing `"""`
+    Acceptable,
+}
+
+impl Accepter for MultiStrAccepter {
+    type Accepter = Self;
+
+    fn acceptable(&self) -> bool {
+        *self == Self::Acceptable
+    }
+
+    fn accept(self, c: char) -> Option<Self> {
+        match self {
+            Self::Unset if c == '"' => Some(Self::Opening(1)),
+            Self::Opening(2) if c == '"' => Some(Self::Any),
+            Self::Opening(quotes) if c == '"' => Some(Self::Opening(quotes + 1)),
+            Self::Closing(2) if c == '"' => Some(Self::Acceptable),
+            Self::Closing(quotes) if c == '"' => Some(Self::Closing(quotes + 1)),
+            Self::Any if c == '"' => Some(Self::Closing(1)),
+            Self::Any | Self::Closing(_) if c == '\\' => Some(Self::EscapeNext),
+            Self::Any | Self::Closing(_) | Self::EscapeNext => Some(Self::Any),
             _ => None,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Raw strings have no escape sequences, so they are delimited by `r`, a number of `#` and `"` and
+/// end at the first `"` followed by as many `#`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RawStrAccepter {
+    #[default]
+    /// The lexeme hasn't started yet
+    Unset,
+    /// The given number of `#` were read after the leading `r`
+    /// Next can be either `#` or `"`
+    Hashes(u32),
+    /// Inside the string delimited by the given number of `#`
+    /// Next can be any char
+    Any(u32),
+    /// A `"` and some `#` were read inside the string, so as many `#` as the first number close it
+    /// Next can be any char
+    Closing(u32, u32),
+    /// The lexeme has its closing `"` and `#`
+    Acceptable,
+}
+
+impl RawStrAccepter {
+    /// The number of `#` delimiting the string read so far.
+    pub fn hashes(self) -> u32 {
+        match self {
+            Self::Hashes(hashes) | Self::Any(hashes) | Self::Closing(hashes, _) => hashes,
+            Self::Unset | Self::Acceptable => 0,
+        }
+    }
+
+    /// Checks if the opening `"` was read but not the closing one yet.
+    pub fn is_open(self) -> bool {
+        matches!(self, Self::Any(_) | Self::Closing(..))
+    }
+}
+
+impl Accepter for RawStrAccepter {
+    type Accepter = Self;
+
+    fn acceptable(&self) -> bool {
+        *self == Self::Acceptable
+    }
+
+    fn accept(self, c: char) -> Option<Self> {
+        match self {
+            Self::Unset if c == 'r' => Some(Self::Hashes(0)),
+            Self::Hashes(hashes) if c == '#' => Some(Self::Hashes(hashes + 1)),
+            Self::Hashes(hashes) if c == '"' => Some(Self::Any(hashes)),
+            Self::Any(0) | Self::Closing(0, _) if c == '"' => Some(Self::Acceptable),
+            Self::Any(hashes) | Self::Closing(hashes, _) if c == '"' => {
+                Some(Self::Closing(hashes, 0))
+            }
+            Self::Closing(hashes, read) if c == '#' && read + 1 == hashes => Some(Self::Acceptable),
+            Self::Closing(hashes, read) if c == '#' => Some(Self::Closing(hashes, read + 1)),
+            Self::Any(hashes) | Self::Closing(hashes, _) => Some(Self::Any(hashes)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum AtomAccepter {
     #[default]
     /// The lexeme hasn't started yet
@@ -405,4 +665,43 @@ mod tests {
         assert!(acp.acceptable());
         assert_eq!(acp.accept('c'), None);
     }
+
+    #[test]
+    fn lex_escapes() {
+        use super::*;
+
+        let accepts = |src: &str| {
+            let mut acp = Some(LitAccepter::Chr(CharAccepter::default()));
+            for c in src.chars() {
+                acp = acp.and_then(|acp| acp.accept(c));
+            }
+            acp.is_some_and(|acp| acp.acceptable())
+        };
+
+        assert!(accepts("'\\n'"));
+        assert!(accepts("'\\''"));
+        assert!(accepts("'\\x7F'"));
+        assert!(accepts("'\\u{1F600}'"));
+        assert!(!accepts("'\\x7'"));
+        assert!(!accepts("'\\u{}'"));
+        assert!(!accepts("'\\u{1234567}'"));
+        assert!(accepts("'é'"));
+        assert!(accepts("'λ'"));
+        assert!(accepts("'😀'"));
+        assert!(!accepts("'\n'"));
+        assert!(!accepts("'ab'"));
+        assert!(!accepts("'''"));
+        assert!(!accepts("''"));
+
+        let mut acp = StrAccepter::default();
+        for c in "\"a\\\"b\"".chars() {
+            acp = acp.accept(c).unwrap();
+        }
+        assert!(acp.acceptable());
+        assert_eq!(StrAccepter::default().accept('\\'), None);
+        assert_eq!(StrAccepter::Acceptable.accept('\\'), None);
+        assert!(StrAccepter::LeadingDoubleQuote
+            .accept('"')
+            .is_some_and(StrAccepter::acceptable));
+    }
 }
@@ -5,7 +5,7 @@ use super::{
 
 const GT: char = '>';
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum OpAccepter {
     /// The lexeme to accept a `:=` operator.
     Decl(DoubleCharAccepter<':', '='>),
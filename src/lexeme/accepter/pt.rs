@@ -1,6 +1,6 @@
 use super::{generics::SingleCharAccepter, Accepter};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PtState {
     Dot(SingleCharAccepter<'.'>),
     Comma(SingleCharAccepter<','>),
@@ -1,6 +1,6 @@
 use super::Accepter;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum WhitespaceAccepter {
     #[default]
     Unset,
@@ -0,0 +1,58 @@
+/// Dedents the `lines` of a multi-line text, so it can be indented along with the code around it.
+///
+/// The first and last lines are dropped if blank, unless there is a single line. Then the
+/// whitespace prefix common to every non-blank line is stripped from every line, which a blank line
+/// only starts with in part at most.
+pub(super) fn dedent(lines: &mut Vec<&str>) {
+    let is_blank = |line: &str| line.trim().is_empty();
+
+    if lines.len() > 1 && is_blank(lines[0]) {
+        lines.remove(0);
+    }
+    if lines.len() > 1 && lines.last().is_some_and(|line| is_blank(line)) {
+        lines.pop();
+    }
+
+    let indent = lines
+        .iter()
+        .filter(|line| !is_blank(line))
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .reduce(common_prefix)
+        .unwrap_or_default();
+
+    for line in lines {
+        *line = &line[common_prefix(line, indent).len()..];
+    }
+}
+
+/// The longest prefix of both `a` and `b`, compared char by char.
+fn common_prefix<'a>(a: &'a str, b: &str) -> &'a str {
+    let len = a
+        .char_indices()
+        .zip(b.chars())
+        .find(|&((_, a), b)| a != b)
+        .map_or(a.len().min(b.len()), |((i, _), _)| i);
+
+    &a[..len]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dedented(text: &str) -> String {
+        let mut lines = text.split('\n').collect();
+        dedent(&mut lines);
+        lines.join("\n")
+    }
+
+    #[test]
+    fn strip_common_prefix() {
+        assert_eq!(dedented("\n    a\n      b\n\n    c\n  "), "a\n  b\n\nc");
+        assert_eq!(dedented("\t\ta\n\t  b"), "\ta\n  b");
+        assert_eq!(dedented("\tx\n    y"), "\tx\n    y");
+        assert_eq!(dedented("\u{a0}x\n y"), "\u{a0}x\n y");
+        assert_eq!(dedented("\u{a0} x\n\u{a0}  y\n \n\u{a0}"), "x\n y\n ");
+        assert_eq!(dedented("   "), "   ");
+    }
+}
@@ -0,0 +1,87 @@
+use std::borrow::Cow;
+
+use super::{dedent::dedent, kind::LexemeKind, Lexeme};
+
+impl<'src> Lexeme<'src> {
+    /// The text of a doc comment, or `None` if the lexeme isn't one.
+    ///
+    /// The `///` or `//!` prefix of a line doc comment is stripped along with the space that
+    /// usually follows it. For `/** */` block doc comments the delimiters, the blank first and last
+    /// lines, the leading `*` of every line (when all lines have one) and the whitespace prefix
+    /// common to every non-blank line are stripped, so only the block comment text has to be
+    /// allocated.
+    pub fn doc_text(&self) -> Option<Cow<'src, str>> {
+        match self.kind.unambiguous()? {
+            LexemeKind::CommentDocOuter | LexemeKind::CommentDocInner => {
+                let text = self.slice[3..].trim_end_matches('\r');
+                Some(Cow::Borrowed(text.strip_prefix(' ').unwrap_or(text)))
+            }
+            LexemeKind::CommentDocBlock => {
+                let text = &self.slice[3..self.slice.len() - 2];
+                Some(Cow::Owned(block_doc_text(text)))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Normalizes the `text` between the delimiters of a block doc comment.
+fn block_doc_text(text: &str) -> String {
+    let is_blank = |line: &str| line.trim().is_empty();
+
+    let mut lines: Vec<_> = text.lines().collect();
+    let decorated = lines
+        .iter()
+        .all(|line| is_blank(line) || line.trim_start().starts_with('*'));
+    if lines.len() > 1 && decorated {
+        for line in &mut lines {
+            *line = line.trim_start().strip_prefix('*').unwrap_or_default();
+        }
+    }
+    dedent(&mut lines);
+
+    lines
+        .iter()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::lex;
+
+    #[test]
+    fn line_doc_text() {
+        let lexemes = lex("/// Adds one\n//! The module\n///\n//// Not a doc\n");
+
+        assert_eq!(lexemes[0].doc_text().as_deref(), Some("Adds one"));
+        assert_eq!(lexemes[2].doc_text().as_deref(), Some("The module"));
+        assert_eq!(lexemes[4].doc_text().as_deref(), Some(""));
+        assert_eq!(lexemes[6].doc_text(), None);
+    }
+
+    #[test]
+    fn block_doc_text() {
+        let lexemes =
+            lex("/** One line */ /**\n     * Adds one\n     *\n     *     x + 1\n     */");
+
+        assert_eq!(lexemes[0].doc_text().as_deref(), Some("One line"));
+        assert_eq!(
+            lexemes[2].doc_text().as_deref(),
+            Some("Adds one\n\n    x + 1")
+        );
+
+        let lexemes = lex("/**\n * Decorated\n */ /**\n\t\tTabs\n\t  and spaces\n*/");
+        assert_eq!(lexemes[0].doc_text().as_deref(), Some("Decorated"));
+        assert_eq!(
+            lexemes[2].doc_text().as_deref(),
+            Some("\tTabs\n  and spaces")
+        );
+
+        let lexemes = lex("/**\n    Indented\n      more\n*/ /**/ /*** x */");
+        assert_eq!(lexemes[0].doc_text().as_deref(), Some("Indented\n  more"));
+        assert_eq!(lexemes[2].doc_text(), None);
+        assert_eq!(lexemes[4].doc_text(), None);
+    }
+}
@@ -0,0 +1,300 @@
+use std::borrow::Cow;
+
+use super::{dedent::dedent, kind::LexemeKind, Lexeme};
+use crate::lexer::error::{LexError, LexErrorKind};
+
+impl<'src> Lexeme<'src> {
+    /// Decodes the value of a `LitStr` lexeme, without the quotes and with every escape sequence
+    /// replaced by the char it stands for, or of a `LitStrRaw` lexeme, without its delimiters.
+    /// The fragments of an interpolated string are decoded like a `LitStr`, without the quotes and
+    /// braces delimiting them.
+    ///
+    /// A `LitStrMulti` lexeme is decoded like a `LitStr` once its contents are dedented: its first
+    /// and last lines are dropped if blank and the whitespace prefix common to every non-blank line
+    /// is stripped, so tabs and spaces don't mix.
+    ///
+    /// The value is borrowed from the source code unless it has escape sequences or spans many
+    /// lines. An invalid escape sequence is reported with the location of the sequence itself.
+    pub fn str_value(&self) -> Result<Cow<'src, str>, LexError> {
+        match self.kind.unambiguous() {
+            Some(
+                LexemeKind::LitStr
+                | LexemeKind::LitStrHead
+                | LexemeKind::LitStrMid
+                | LexemeKind::LitStrTail,
+            ) => self.unescape(1, self.slice.len() - 1),
+            Some(LexemeKind::LitStrMulti) => {
+                // Escape sequences are checked first so errors point into the source code
+                self.check_escapes(3, self.slice.len() - 3)?;
+
+                let contents = dedent_str(&self.slice[3..self.slice.len() - 3]);
+                unescape(&contents)
+                    .map(|value| Cow::Owned(value.into_owned()))
+                    .map_err(|(kind, _, _)| self.error(kind))
+            }
+            Some(LexemeKind::LitStrRaw) => {
+                let hashes = self.slice[1..].find('"').unwrap_or_default();
+                Ok(Cow::Borrowed(
+                    &self.slice[hashes + 2..self.slice.len() - hashes - 1],
+                ))
+            }
+            _ => Err(self.error(LexErrorKind::NotALiteral)),
+        }
+    }
+
+    /// Checks the escape sequences of a string lexeme, failing just like [`Lexeme::str_value`]
+    /// but without decoding the value, so nothing is allocated.
+    pub(crate) fn check_str_value(&self) -> Result<(), LexError> {
+        match self.kind.unambiguous() {
+            Some(
+                LexemeKind::LitStr
+                | LexemeKind::LitStrHead
+                | LexemeKind::LitStrMid
+                | LexemeKind::LitStrTail,
+            ) => self.check_escapes(1, self.slice.len() - 1),
+            Some(LexemeKind::LitStrMulti) => self.check_escapes(3, self.slice.len() - 3),
+            Some(LexemeKind::LitStrRaw) => Ok(()),
+            _ => Err(self.error(LexErrorKind::NotALiteral)),
+        }
+    }
+
+    /// Decodes the value of a `LitChr` lexeme, replacing an escape sequence by the char it stands
+    /// for.
+    pub fn chr_value(&self) -> Result<char, LexError> {
+        if self.kind.unambiguous() != Some(LexemeKind::LitChr) {
+            return Err(self.error(LexErrorKind::NotALiteral));
+        }
+
+        let value = self.unescape(1, self.slice.len() - 1)?;
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(self.error(LexErrorKind::MalformedEscape)),
+        }
+    }
+
+    /// Replaces the escape sequences in the slice from `start` to `end` (relative to the lexeme).
+    fn unescape(&self, start: usize, end: usize) -> Result<Cow<'src, str>, LexError> {
+        unescape(&self.slice[start..end]).map_err(|error| self.escape_error(start, error))
+    }
+
+    /// Checks the escape sequences in the slice from `start` to `end` (relative to the lexeme).
+    fn check_escapes(&self, start: usize, end: usize) -> Result<(), LexError> {
+        unescape_into(&self.slice[start..end], None)
+            .map_err(|error| self.escape_error(start, error))
+    }
+
+    /// Locates the `error` of an escape sequence in the slice starting at `start`.
+    fn escape_error(&self, start: usize, (kind, error_start, error_end): EscapeError) -> LexError {
+        self.error_at(kind, start + error_start, start + error_end)
+    }
+}
+
+/// An invalid escape sequence, along with its start and end.
+type EscapeError = (LexErrorKind, usize, usize);
+
+/// Replaces the escape sequences in `contents`, or returns the error along with the start and end
+/// of the invalid sequence in `contents`.
+fn unescape(contents: &str) -> Result<Cow<'_, str>, EscapeError> {
+    if !contents.contains('\\') {
+        return Ok(Cow::Borrowed(contents));
+    }
+
+    let mut value = String::with_capacity(contents.len());
+    unescape_into(contents, Some(&mut value))?;
+
+    Ok(Cow::Owned(value))
+}
+
+/// Replaces the escape sequences in `contents`, pushing the result to `value`, or only checks them
+/// without a `value`.
+fn unescape_into(contents: &str, mut value: Option<&mut String>) -> Result<(), EscapeError> {
+    let mut rest = contents;
+    while let Some(i) = rest.find('\\') {
+        let escape = &rest[i + 1..];
+        match parse_escape(escape) {
+            Ok((c, len)) => {
+                if let Some(value) = value.as_deref_mut() {
+                    value.push_str(&rest[..i]);
+                    value.push(c);
+                }
+                rest = &escape[len..];
+            }
+            Err((kind, len)) => {
+                let at = contents.len() - rest.len() + i;
+                return Err((kind, at, at + 1 + len));
+            }
+        }
+    }
+    if let Some(value) = value {
+        value.push_str(rest);
+    }
+
+    Ok(())
+}
+
+/// Dedents the `contents` of a multi-line string (see [`dedent`]), so the string can be indented
+/// along with the code around it. Line breaks are normalized to `\n`.
+fn dedent_str(contents: &str) -> String {
+    let mut lines: Vec<_> = contents
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect();
+    dedent(&mut lines);
+
+    lines.join("\n")
+}
+
+/// Parses the escape sequence at the start of `escape`, which follows a `\`.
+///
+/// Returns the escaped char and the length of the sequence, or the error and the length of the
+/// invalid sequence.
+fn parse_escape(escape: &str) -> Result<(char, usize), (LexErrorKind, usize)> {
+    let c = escape
+        .chars()
+        .next()
+        .ok_or((LexErrorKind::MalformedEscape, 0))?;
+
+    match c {
+        'n' => Ok(('\n', 1)),
+        'r' => Ok(('\r', 1)),
+        't' => Ok(('\t', 1)),
+        '0' => Ok(('\0', 1)),
+        '\\' | '"' | '\'' | '{' | '}' => Ok((c, 1)),
+        'x' => {
+            let value = escape
+                .get(1..3)
+                .filter(|digits| digits.chars().all(|c| c.is_ascii_hexdigit()))
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .ok_or((LexErrorKind::MalformedEscape, 1))?;
+
+            if value.is_ascii() {
+                Ok((value as char, 3))
+            } else {
+                Err((LexErrorKind::MalformedEscape, 3))
+            }
+        }
+        'u' => {
+            let end = escape
+                .find('}')
+                .filter(|_| escape[1..].starts_with('{'))
+                .ok_or((LexErrorKind::MalformedEscape, 1))?;
+            let digits = &escape[2..end];
+
+            if (1..=6).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                u32::from_str_radix(digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .map(|c| (c, end + 1))
+                    .ok_or((LexErrorKind::MalformedEscape, end + 1))
+            } else {
+                Err((LexErrorKind::MalformedEscape, end + 1))
+            }
+        }
+        c => Err((LexErrorKind::UnknownEscape(c), c.len_utf8())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexeme::Coord, lexer::lex};
+
+    #[test]
+    fn decode_str() {
+        let lexemes = lex(r#""plain" "a\n\t\\\"\'\0\x7F\u{1F600}b""#);
+
+        assert!(matches!(lexemes[0].str_value(), Ok(Cow::Borrowed("plain"))));
+        assert_eq!(
+            lexemes[2].str_value().as_deref(),
+            Ok("a\n\t\\\"'\0\x7F\u{1F600}b")
+        );
+    }
+
+    #[test]
+    fn decode_raw_str() {
+        let lexemes = lex(r####"r"C:\path" r#"say "hi""# r##"a "# b"##"####);
+
+        assert!(matches!(
+            lexemes[0].str_value(),
+            Ok(Cow::Borrowed(r"C:\path"))
+        ));
+        assert_eq!(lexemes[2].str_value().as_deref(), Ok(r#"say "hi""#));
+        assert_eq!(lexemes[4].str_value().as_deref(), Ok(r##"a "# b"##));
+    }
+
+    #[test]
+    fn decode_multi_line_str() {
+        let src = "val s := \"\"\"\n    Hello\n      \\\"world\\\"\n\n    bye\n    \"\"\"";
+        let lexemes = lex(src);
+        assert_eq!(
+            lexemes[6].str_value().as_deref(),
+            Ok("Hello\n  \"world\"\n\nbye")
+        );
+
+        let lexemes = lex(r#""""one "line" only""""#);
+        assert_eq!(lexemes[0].str_value().as_deref(), Ok(r#"one "line" only"#));
+
+        let lexemes = lex("\"\"\"\n\u{a0}x\n y\n\"\"\" \"\"\"\n\tx\n    y\n\t\"\"\"");
+        assert_eq!(lexemes[0].str_value().as_deref(), Ok("\u{a0}x\n y"));
+        assert_eq!(lexemes[2].str_value().as_deref(), Ok("\tx\n    y"));
+
+        let error = lex("\"\"\"\n  a\n  \\q\"\"\"")[0].str_value().unwrap_err();
+        assert_eq!(error.kind, LexErrorKind::UnknownEscape('q'));
+        assert_eq!(error.start_coord, Coord { line: 3, col: 3 });
+    }
+
+    #[test]
+    fn decode_chr() {
+        let lexemes = lex(r"'a' '\n' '\'' '\x41' '\u{e9}' 'λ' '😀'");
+        let values: Vec<_> = lexemes
+            .iter()
+            .step_by(2)
+            .map(|lexeme| lexeme.chr_value())
+            .collect();
+
+        assert_eq!(
+            values,
+            [
+                Ok('a'),
+                Ok('\n'),
+                Ok('\''),
+                Ok('A'),
+                Ok('é'),
+                Ok('λ'),
+                Ok('😀')
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_escapes() {
+        let lexemes = lex("\"ok\nthen \\q\" \"\\x80\" \"\\u{D800}\" '\\z'");
+
+        let error = lexemes[0].str_value().unwrap_err();
+        assert_eq!(error.kind, LexErrorKind::UnknownEscape('q'));
+        assert_eq!((error.start, error.end), (9, 11));
+        assert_eq!(error.start_coord, Coord { line: 2, col: 6 });
+        assert_eq!(error.end_coord, Coord { line: 2, col: 8 });
+
+        let error = lexemes[2].str_value().unwrap_err();
+        assert_eq!(error.kind, LexErrorKind::MalformedEscape);
+        assert_eq!((error.start, error.end), (14, 18));
+
+        let error = lexemes[4].str_value().unwrap_err();
+        assert_eq!(error.kind, LexErrorKind::MalformedEscape);
+        assert_eq!((error.start, error.end), (21, 29));
+
+        for lexeme in &lexemes[..6] {
+            assert_eq!(lexeme.check_str_value(), lexeme.str_value().map(drop));
+        }
+
+        let error = lexemes[6].chr_value().unwrap_err();
+        assert_eq!(error.kind, LexErrorKind::UnknownEscape('z'));
+
+        assert_eq!(
+            lex("x")[0].str_value().unwrap_err().kind,
+            LexErrorKind::NotALiteral
+        );
+    }
+}
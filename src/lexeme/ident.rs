@@ -0,0 +1,94 @@
+use std::borrow::Cow;
+
+use super::{kind::LexemeKind, Lexeme};
+
+impl<'src> Lexeme<'src> {
+    /// The name of a value or type identifier, or `None` if the lexeme can't be one.
+    ///
+    /// With the `unicode-idents` feature the name is NFC normalized, so names that look the same
+    /// compare equal even if they were written with different code points, and it is only owned if
+    /// the slice wasn't normalized already.
+    pub fn ident_name(&self) -> Option<Cow<'src, str>> {
+        let kinds = self.kind.kinds();
+        if !kinds.contains(LexemeKind::IdentVal) && !kinds.contains(LexemeKind::IdentType) {
+            return None;
+        }
+
+        Some(normalize(self.slice))
+    }
+}
+
+#[cfg(feature = "unicode-idents")]
+fn normalize(name: &str) -> Cow<'_, str> {
+    use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+    if is_nfc(name) {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(name.nfc().collect())
+    }
+}
+
+#[cfg(not(feature = "unicode-idents"))]
+fn normalize(name: &str) -> Cow<'_, str> {
+    Cow::Borrowed(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::lex;
+
+    #[test]
+    fn ascii_ident_names() {
+        let lexemes = lex("val x := Type");
+
+        assert_eq!(lexemes[0].ident_name().as_deref(), Some("val"));
+        assert_eq!(lexemes[2].ident_name().as_deref(), Some("x"));
+        assert_eq!(lexemes[4].ident_name(), None);
+        assert_eq!(lexemes[6].ident_name().as_deref(), Some("Type"));
+    }
+
+    #[cfg(feature = "unicode-idents")]
+    #[test]
+    fn unicode_idents() {
+        use crate::lexeme::kind::LexemeKind;
+
+        let lexemes = lex("größe Ñandú λ 日本 x→y aÑ éÉ");
+        let kinds: Vec<_> = lexemes
+            .iter()
+            .map(|lexeme| (lexeme.slice, lexeme.kind.unambiguous()))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            [
+                ("größe", Some(LexemeKind::IdentVal)),
+                (" ", Some(LexemeKind::Ws)),
+                ("Ñandú", Some(LexemeKind::IdentType)),
+                (" ", Some(LexemeKind::Ws)),
+                ("λ", Some(LexemeKind::IdentVal)),
+                (" ", Some(LexemeKind::Ws)),
+                ("日本", Some(LexemeKind::IdentVal)),
+                (" ", Some(LexemeKind::Ws)),
+                ("x", Some(LexemeKind::IdentVal)),
+                ("y", Some(LexemeKind::IdentVal)),
+                (" ", Some(LexemeKind::Ws)),
+                ("a", Some(LexemeKind::IdentVal)),
+                ("Ñ", Some(LexemeKind::IdentType)),
+                (" ", Some(LexemeKind::Ws)),
+                ("é", Some(LexemeKind::IdentVal)),
+                ("É", Some(LexemeKind::IdentType)),
+            ]
+        );
+    }
+
+    #[cfg(feature = "unicode-idents")]
+    #[test]
+    fn nfc_ident_names() {
+        // `é` precomposed and as `e` followed by a combining acute accent
+        let lexemes = lex("caf\u{e9} cafe\u{301}");
+
+        assert_eq!(lexemes[0].ident_name(), lexemes[2].ident_name());
+        assert_ne!(lexemes[0].slice, lexemes[2].slice);
+    }
+}
@@ -1,6 +1,7 @@
-use super::accepter::{self, ident, kw, LexemeAccepter};
+use super::accepter::{self, ident, kw, lit, LexemeAccepter};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
 pub enum LexemeKind {
     KwVal,
     KwFn,
@@ -59,6 +60,14 @@ pub enum LexemeKind {
     LitFlt,
     LitChr,
     LitStr,
+    LitStrRaw,
+    LitStrMulti,
+    /// The start of an interpolated string up to the `{` of its first hole, like `"a {`.
+    LitStrHead,
+    /// The part of an interpolated string between two holes, like `} b {`.
+    LitStrMid,
+    /// The end of an interpolated string after its last hole, like `} c"`.
+    LitStrTail,
     LitAtom,
     PtDot,
     PtComma,
@@ -67,6 +76,170 @@ pub enum LexemeKind {
     Ws,
     CommentLine,
     CommentBlock,
+    /// A `///` doc comment, documenting what follows it.
+    CommentDocOuter,
+    /// A `//!` doc comment, documenting what encloses it.
+    CommentDocInner,
+    /// A `/** */` doc comment, documenting what follows it.
+    CommentDocBlock,
+    /// The end of the source code, only produced on demand.
+    Eof,
+    /// Text that can't be lexed, only produced by
+    /// [`lex_with_trivia`](crate::lexer::trivia::lex_with_trivia) in place of the skipped text.
+    Unknown,
+}
+
+impl LexemeKind {
+    /// Every lexeme kind, in declaration order.
+    pub const ALL: [Self; 75] = [
+        Self::KwVal,
+        Self::KwFn,
+        Self::KwType,
+        Self::KwTag,
+        Self::KwMain,
+        Self::KwMacro,
+        Self::KwImport,
+        Self::KwObject,
+        Self::IdentVal,
+        Self::IdentType,
+        Self::IdentTag,
+        Self::IdentMacro,
+        Self::IdentSubtype,
+        Self::OpDecl,
+        Self::OpEq,
+        Self::OpPlus,
+        Self::OpMinus,
+        Self::OpStar,
+        Self::OpSlash,
+        Self::OpCaret,
+        Self::OpUScore,
+        Self::OpPercent,
+        Self::OpAnd,
+        Self::OpAndAnd,
+        Self::OpOr,
+        Self::OpOrOr,
+        Self::OpNot,
+        Self::OpNotEq,
+        Self::OpEqEq,
+        Self::OpGt,
+        Self::OpGtEq,
+        Self::OpLt,
+        Self::OpLtEq,
+        Self::OpLtLt,
+        Self::OpGtGt,
+        Self::OpRArw,
+        Self::OpFatRArw,
+        Self::OpTilde,
+        Self::OpJoin,
+        Self::OpBSlash,
+        Self::OpRange,
+        Self::OpCRange,
+        Self::OpSpread,
+        Self::OpDollarDollar,
+        Self::DelimOParen,
+        Self::DelimCParen,
+        Self::DelimOBrack,
+        Self::DelimCBrack,
+        Self::DelimOBrace,
+        Self::DelimCBrace,
+        Self::LitIntDec,
+        Self::LitIntBin,
+        Self::LitIntOct,
+        Self::LitIntHex,
+        Self::LitFlt,
+        Self::LitChr,
+        Self::LitStr,
+        Self::LitStrRaw,
+        Self::LitStrMulti,
+        Self::LitStrHead,
+        Self::LitStrMid,
+        Self::LitStrTail,
+        Self::LitAtom,
+        Self::PtDot,
+        Self::PtComma,
+        Self::PtColon,
+        Self::PtSemi,
+        Self::Ws,
+        Self::CommentLine,
+        Self::CommentBlock,
+        Self::CommentDocOuter,
+        Self::CommentDocInner,
+        Self::CommentDocBlock,
+        Self::Eof,
+        Self::Unknown,
+    ];
+
+    /// Checks if the kind is a keyword.
+    pub fn is_keyword(self) -> bool {
+        matches!(
+            self,
+            Self::KwVal
+                | Self::KwFn
+                | Self::KwType
+                | Self::KwTag
+                | Self::KwMain
+                | Self::KwMacro
+                | Self::KwImport
+                | Self::KwObject
+        )
+    }
+
+    /// Checks if the kind is whitespace, a comment or text that can't be lexed, which have no
+    /// meaning for the parser.
+    pub fn is_trivia(self) -> bool {
+        matches!(self, Self::Ws | Self::Unknown) || self.is_comment()
+    }
+
+    /// Checks if the kind is a string literal, be it regular, raw or multi-line, or a fragment of
+    /// an interpolated string.
+    pub fn is_str_literal(self) -> bool {
+        matches!(
+            self,
+            Self::LitStr
+                | Self::LitStrRaw
+                | Self::LitStrMulti
+                | Self::LitStrHead
+                | Self::LitStrMid
+                | Self::LitStrTail
+        )
+    }
+
+    /// Checks if the kind is a comment, including doc comments.
+    pub fn is_comment(self) -> bool {
+        matches!(
+            self,
+            Self::CommentLine
+                | Self::CommentBlock
+                | Self::CommentDocOuter
+                | Self::CommentDocInner
+                | Self::CommentDocBlock
+        )
+    }
+
+    /// Checks if the kind is a doc comment.
+    pub fn is_doc_comment(self) -> bool {
+        matches!(
+            self,
+            Self::CommentDocOuter | Self::CommentDocInner | Self::CommentDocBlock
+        )
+    }
+
+    /// Checks if the kind is an integer or floating point literal.
+    pub fn is_numeric_literal(self) -> bool {
+        matches!(
+            self,
+            Self::LitIntDec | Self::LitIntBin | Self::LitIntOct | Self::LitIntHex | Self::LitFlt
+        )
+    }
+}
+
+impl TryFrom<u8> for LexemeKind {
+    type Error = u8;
+
+    /// Gets the kind whose discriminant is `value`, if any.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::ALL.get(value as usize).copied().ok_or(value)
+    }
 }
 
 impl From<LexemeAccepter> for LexemeKind {
@@ -137,7 +310,14 @@ impl From<LexemeAccepter> for LexemeKind {
                 accepter::lit::LitAccepter::IntHex(_) => Self::LitIntHex,
                 accepter::lit::LitAccepter::Flt(_) => Self::LitFlt,
                 accepter::lit::LitAccepter::Chr(_) => Self::LitChr,
+                accepter::lit::LitAccepter::Str(lit::StrAccepter::HoleStart) => Self::LitStrHead,
                 accepter::lit::LitAccepter::Str(_) => Self::LitStr,
+                accepter::lit::LitAccepter::StrCont(lit::StrContAccepter::Resumed(
+                    lit::StrAccepter::HoleStart,
+                )) => Self::LitStrMid,
+                accepter::lit::LitAccepter::StrCont(_) => Self::LitStrTail,
+                accepter::lit::LitAccepter::StrRaw(_) => Self::LitStrRaw,
+                accepter::lit::LitAccepter::StrMulti(_) => Self::LitStrMulti,
                 accepter::lit::LitAccepter::Atom(_) => Self::LitAtom,
             },
             LexemeAccepter::Pt(pt_state) => match pt_state {
@@ -148,9 +328,127 @@ impl From<LexemeAccepter> for LexemeKind {
             },
             LexemeAccepter::Ws(_) => Self::Ws,
             LexemeAccepter::Comment(comment_state) => match comment_state {
+                accepter::comment::CommentAccepter::Line(state) if state.is_doc_outer() => {
+                    Self::CommentDocOuter
+                }
+                accepter::comment::CommentAccepter::Line(state) if state.is_doc_inner() => {
+                    Self::CommentDocInner
+                }
                 accepter::comment::CommentAccepter::Line(_) => Self::CommentLine,
+                accepter::comment::CommentAccepter::Block(state) if state.is_doc() => {
+                    Self::CommentDocBlock
+                }
                 accepter::comment::CommentAccepter::Block(_) => Self::CommentBlock,
             },
         }
     }
 }
+
+/// A set of lexeme kinds.
+///
+/// It is a bitset indexed by the kinds discriminants, so it is `Copy` and iterating over it
+/// yields the kinds in declaration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct LexemeKindSet(u128);
+
+impl LexemeKindSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    /// Adds `kind` to the set.
+    pub fn insert(&mut self, kind: LexemeKind) {
+        self.0 |= 1 << kind as u8;
+    }
+
+    /// Removes `kind` from the set.
+    pub fn remove(&mut self, kind: LexemeKind) {
+        self.0 &= !(1 << kind as u8);
+    }
+
+    /// Checks if `kind` is in the set.
+    pub fn contains(self, kind: LexemeKind) -> bool {
+        self.0 & (1 << kind as u8) != 0
+    }
+
+    /// The number of kinds in the set.
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Checks if the set has no kinds.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The first kind in the set, in declaration order.
+    pub fn first(self) -> Option<LexemeKind> {
+        self.iter().next()
+    }
+
+    /// Iterates over the kinds in the set, in declaration order.
+    pub fn iter(self) -> impl Iterator<Item = LexemeKind> {
+        LexemeKind::ALL
+            .into_iter()
+            .filter(move |&kind| self.contains(kind))
+    }
+
+    /// Returns the subset of kinds for which `predicate` holds.
+    pub fn filter(self, mut predicate: impl FnMut(LexemeKind) -> bool) -> Self {
+        self.iter().filter(|&kind| predicate(kind)).collect()
+    }
+}
+
+impl FromIterator<LexemeKind> for LexemeKindSet {
+    fn from_iter<T: IntoIterator<Item = LexemeKind>>(iter: T) -> Self {
+        let mut set = Self::new();
+        for kind in iter {
+            set.insert(kind);
+        }
+        set
+    }
+}
+
+impl<const N: usize> From<[LexemeKind; N]> for LexemeKindSet {
+    fn from(kinds: [LexemeKind; N]) -> Self {
+        kinds.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kinds_round_trip_through_u8() {
+        for kind in LexemeKind::ALL {
+            assert_eq!(LexemeKind::try_from(kind as u8), Ok(kind));
+        }
+        assert!(LexemeKind::try_from(LexemeKind::ALL.len() as u8).is_err());
+    }
+
+    #[test]
+    fn kind_set() {
+        let mut set = LexemeKindSet::from([LexemeKind::IdentVal, LexemeKind::KwVal]);
+        set.insert(LexemeKind::LitAtom);
+
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(LexemeKind::KwVal));
+        assert!(!set.contains(LexemeKind::KwFn));
+        assert_eq!(set.first(), Some(LexemeKind::KwVal));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [LexemeKind::KwVal, LexemeKind::IdentVal, LexemeKind::LitAtom]
+        );
+        assert_eq!(
+            set.filter(|kind| !kind.is_keyword()),
+            LexemeKindSet::from([LexemeKind::IdentVal, LexemeKind::LitAtom])
+        );
+
+        set.remove(LexemeKind::KwVal);
+        set.remove(LexemeKind::IdentVal);
+        set.remove(LexemeKind::LitAtom);
+        assert!(set.is_empty());
+    }
+}
@@ -0,0 +1,325 @@
+use std::fmt::Display;
+
+use super::{kind::LexemeKind, Lexeme};
+use crate::lexer::error::{LexError, LexErrorKind};
+
+/// The integer type declared by the suffix of an integer literal, like `U8` or `I64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntTy {
+    pub signed: bool,
+    /// The width in bits, one of 8, 16, 32 or 64.
+    pub width: u8,
+}
+
+impl IntTy {
+    /// The largest value of the type.
+    pub fn max(self) -> u128 {
+        if self.signed {
+            (1 << (self.width - 1)) - 1
+        } else {
+            (1 << self.width) - 1
+        }
+    }
+}
+
+impl Display for IntTy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.signed { 'I' } else { 'U' };
+        write!(f, "{}{}", sign, self.width)
+    }
+}
+
+/// The decoded value of an integer literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntLit {
+    pub value: u128,
+    /// The type declared by the suffix, if any.
+    pub ty: Option<IntTy>,
+}
+
+impl IntLit {
+    /// The value as a signed integer, `None` if it doesn't fit in an `i128`.
+    pub fn as_i128(self) -> Option<i128> {
+        i128::try_from(self.value).ok()
+    }
+}
+
+/// The floating point type declared by the suffix of a floating point literal, `F32` or `F64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FltTy {
+    F32,
+    F64,
+}
+
+impl Display for FltTy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::F32 => write!(f, "F32"),
+            Self::F64 => write!(f, "F64"),
+        }
+    }
+}
+
+/// The decoded value of a floating point literal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FltLit {
+    /// The value, rounded to the precision of the declared type.
+    pub value: f64,
+    /// The type declared by the suffix, if any.
+    pub ty: Option<FltTy>,
+}
+
+impl Lexeme<'_> {
+    /// Decodes the value of a `LitIntDec`, `LitIntBin`, `LitIntOct` or `LitIntHex` lexeme.
+    ///
+    /// Literals have no sign, so a value out of the range of its declared type (like `300U8` or
+    /// `128I8`) or not fitting in 128 bits is reported as an [`LexErrorKind::IntOverflow`]. Any
+    /// other lexeme is reported as [`LexErrorKind::NotALiteral`].
+    pub fn int_value(&self) -> Result<IntLit, LexError> {
+        let (radix, digits) = match self.kind.unambiguous() {
+            Some(LexemeKind::LitIntDec) => (10, self.slice),
+            Some(LexemeKind::LitIntBin) => (2, &self.slice[2..]),
+            Some(LexemeKind::LitIntOct) => (8, &self.slice[2..]),
+            Some(LexemeKind::LitIntHex) => (16, &self.slice[2..]),
+            _ => return Err(self.error(LexErrorKind::NotALiteral)),
+        };
+
+        let (digits, ty) = match digits.find(['U', 'I']) {
+            Some(i) => {
+                let ty = parse_int_ty(&digits[i..])
+                    .ok_or_else(|| self.error(LexErrorKind::MalformedNumber))?;
+                (&digits[..i], Some(ty))
+            }
+            None => (digits, None),
+        };
+
+        let mut value: u128 = 0;
+        for c in digits.chars().filter(|&c| c != '_') {
+            let digit = c
+                .to_digit(radix)
+                .ok_or_else(|| self.error(LexErrorKind::MalformedNumber))?;
+            value = value
+                .checked_mul(radix as u128)
+                .and_then(|value| value.checked_add(digit as u128))
+                .ok_or_else(|| self.error(LexErrorKind::IntOverflow(ty)))?;
+        }
+
+        match ty {
+            Some(ty) if value > ty.max() => Err(self.error(LexErrorKind::IntOverflow(Some(ty)))),
+            _ => Ok(IntLit { value, ty }),
+        }
+    }
+
+    /// Decodes the value of a `LitFlt` lexeme.
+    ///
+    /// A value too large for its declared type, or for an `F64` if it has none, is reported as an
+    /// [`LexErrorKind::FltOverflow`]. Any other lexeme is reported as
+    /// [`LexErrorKind::NotALiteral`].
+    pub fn flt_value(&self) -> Result<FltLit, LexError> {
+        if self.kind.unambiguous() != Some(LexemeKind::LitFlt) {
+            return Err(self.error(LexErrorKind::NotALiteral));
+        }
+
+        let (digits, ty) = match self.slice.find('F') {
+            Some(i) => {
+                let ty = match &self.slice[i..] {
+                    "F32" => FltTy::F32,
+                    "F64" => FltTy::F64,
+                    _ => return Err(self.error(LexErrorKind::MalformedNumber)),
+                };
+                (&self.slice[..i], Some(ty))
+            }
+            None => (self.slice, None),
+        };
+
+        let digits = digits.replace('_', "");
+        let value = match ty {
+            Some(FltTy::F32) => digits.parse::<f32>().map(f64::from),
+            _ => digits.parse::<f64>(),
+        }
+        .map_err(|_| self.error(LexErrorKind::MalformedNumber))?;
+
+        if value.is_finite() {
+            Ok(FltLit { value, ty })
+        } else {
+            Err(self.error(LexErrorKind::FltOverflow(ty)))
+        }
+    }
+}
+
+/// Parses an integer type suffix like `U8` or `I64`.
+fn parse_int_ty(suffix: &str) -> Option<IntTy> {
+    let signed = match suffix.chars().next()? {
+        'U' => false,
+        'I' => true,
+        _ => return None,
+    };
+    let width = match &suffix[1..] {
+        "8" => 8,
+        "16" => 16,
+        "32" => 32,
+        "64" => 64,
+        _ => return None,
+    };
+
+    Some(IntTy { signed, width })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lex;
+
+    fn int(src: &str) -> Result<IntLit, LexErrorKind> {
+        lex(src)[0].int_value().map_err(|error| error.kind)
+    }
+
+    #[test]
+    fn decode_int() {
+        let u8_ty = Some(IntTy {
+            signed: false,
+            width: 8,
+        });
+        let i64_ty = Some(IntTy {
+            signed: true,
+            width: 64,
+        });
+
+        assert_eq!(
+            int("42"),
+            Ok(IntLit {
+                value: 42,
+                ty: None
+            })
+        );
+        assert_eq!(
+            int("1_000"),
+            Ok(IntLit {
+                value: 1000,
+                ty: None
+            })
+        );
+        assert_eq!(
+            int("255U8"),
+            Ok(IntLit {
+                value: 255,
+                ty: u8_ty
+            })
+        );
+        assert_eq!(
+            int("7I64"),
+            Ok(IntLit {
+                value: 7,
+                ty: i64_ty
+            })
+        );
+        assert_eq!(int("0b1_01"), Ok(IntLit { value: 5, ty: None }));
+        assert_eq!(
+            int("0o17"),
+            Ok(IntLit {
+                value: 15,
+                ty: None
+            })
+        );
+        assert_eq!(
+            int("0xfF"),
+            Ok(IntLit {
+                value: 255,
+                ty: None
+            })
+        );
+        assert_eq!(
+            int("0xFFU8"),
+            Ok(IntLit {
+                value: 255,
+                ty: u8_ty
+            })
+        );
+        assert_eq!(int("300U8"), Err(LexErrorKind::IntOverflow(u8_ty)));
+        assert_eq!(int("0x100U8"), Err(LexErrorKind::IntOverflow(u8_ty)));
+        assert_eq!(
+            int("0b1010I16"),
+            Ok(IntLit {
+                value: 10,
+                ty: Some(IntTy {
+                    signed: true,
+                    width: 16
+                })
+            })
+        );
+        assert_eq!(int("0o400U8"), Err(LexErrorKind::IntOverflow(u8_ty)));
+        assert_eq!(
+            int("128I8"),
+            Err(LexErrorKind::IntOverflow(Some(IntTy {
+                signed: true,
+                width: 8
+            })))
+        );
+        assert_eq!(
+            int("340282366920938463463374607431768211456"),
+            Err(LexErrorKind::IntOverflow(None))
+        );
+        assert_eq!(int("x"), Err(LexErrorKind::NotALiteral));
+    }
+
+    #[test]
+    fn decode_flt() {
+        let flt = |src: &str| lex(src)[0].flt_value().map_err(|error| error.kind);
+
+        assert_eq!(
+            flt("3.25"),
+            Ok(FltLit {
+                value: 3.25,
+                ty: None
+            })
+        );
+        assert_eq!(
+            flt("1_000.5"),
+            Ok(FltLit {
+                value: 1000.5,
+                ty: None
+            })
+        );
+        assert_eq!(
+            flt("1e10"),
+            Ok(FltLit {
+                value: 1e10,
+                ty: None
+            })
+        );
+        assert_eq!(
+            flt("6.02e-23"),
+            Ok(FltLit {
+                value: 6.02e-23,
+                ty: None
+            })
+        );
+        assert_eq!(
+            flt("2E+1_0F64"),
+            Ok(FltLit {
+                value: 2e10,
+                ty: Some(FltTy::F64)
+            })
+        );
+        assert_eq!(
+            flt("0.1F32"),
+            Ok(FltLit {
+                value: f64::from(0.1f32),
+                ty: Some(FltTy::F32)
+            })
+        );
+        assert_eq!(
+            flt("1F32"),
+            Ok(FltLit {
+                value: 1.0,
+                ty: Some(FltTy::F32)
+            })
+        );
+        assert_eq!(
+            flt("1e39F32"),
+            Err(LexErrorKind::FltOverflow(Some(FltTy::F32)))
+        );
+        assert_eq!(flt("1e400"), Err(LexErrorKind::FltOverflow(None)));
+        assert_eq!(flt("3"), Err(LexErrorKind::NotALiteral));
+    }
+}
@@ -1,61 +1,360 @@
 use crate::{
     lexeme::{
-        accepter::{Accepter, LexemeAccepter},
-        Coord, Lexeme,
+        accepter::{lit::LitAccepter, LexemeAccepter},
+        kind::LexemeKindSet,
+        Coord, Lexeme, LexemeAmbiguity,
     },
     LexemeKind,
 };
 
-/// The delimiters in the source code.
-/// Those can be used to separate lexemes.
+use std::iter::FusedIterator;
+
+use dfa::Dfa;
+use error::{LexError, LexErrorKind};
+
+pub mod buffer;
+mod dfa;
+pub mod disambiguation;
+pub mod error;
+pub mod incremental;
+pub mod lint;
+pub mod tree;
+pub mod trivia;
+
+// The delimiters in the source code.
+// Those can be used to separate lexemes.
 //const DELIMITERS: [char; 14] = ['(', ')', '[', ']', '{', '}', ',', ':', ';', '.', ' ', '\n', '\t', '\r'];
 
 /// The Aura lexer. It takes a source code string and returns a vector of lexemes groups.
 /// As of now, some lexemes might be ambiguous, so the lexer return all possible lexemes and
 /// the parser will have to disambiguate them using the nearby lexemes.
+///
+/// Text that can't be lexed is skipped, use [`lex_with_errors`] to know about it.
 pub fn lex(src: &str) -> Vec<Lexeme<'_>> {
-    let chars = src.chars().enumerate();
-    let mut start = 0;
-    let mut start_coord = Coord { line: 1, col: 1 };
-    let mut end_coord: Coord = start_coord;
-
-    let mut candidates = LexemeAccepter::stream();
-    let mut lexemes = vec![];
-
-    for (i, c) in chars {
-        let next_candidates = get_next_candidates(&candidates, c);
-
-        if acceptable_candidates_count(&next_candidates) == 0
-            && acceptable_candidates_count(&candidates) > 0
-        {
-            lexemes.push(build_lexeme_from_candidates(
-                candidates,
-                src,
-                start,
-                i,
-                start_coord,
-                end_coord,
-            ));
-
-            candidates = get_next_candidates(&LexemeAccepter::stream(), c);
-
-            start = i;
-            start_coord = end_coord;
-        } else {
-            candidates = next_candidates;
+    Lexer::new(src).collect()
+}
+
+/// The Aura lexer reporting errors. It works just like [`lex`] but also returns every
+/// [`LexError`] found along the way.
+///
+/// The text of an error is skipped and the lexing resumes right after it, so a single
+/// call reports as many errors as possible.
+pub fn lex_with_errors(src: &str) -> (Vec<Lexeme<'_>>, Vec<LexError>) {
+    let mut lexer = Lexer::new(src);
+    let lexemes = lexer.by_ref().collect();
+
+    (lexemes, lexer.into_errors())
+}
+
+/// The Aura lexer with an end of input sentinel. It works just like [`lex`] but the last lexeme
+/// is always an empty [`LexemeKind::Eof`] located at the end of the source code.
+pub fn lex_with_eof(src: &str) -> Vec<Lexeme<'_>> {
+    Lexer::new(src).with_eof().collect()
+}
+
+/// The Aura lazy lexer. It lexes the source code one lexeme at a time as it is iterated, so the
+/// parser can pull lexemes on demand and stop whenever it wants to.
+///
+/// The errors found so far are kept by the lexer and can be checked at any time with
+/// [`Lexer::errors`].
+#[derive(Debug, Clone)]
+pub struct Lexer<'src> {
+    src: &'src str,
+    /// The byte offset where the next lexeme starts.
+    pos: usize,
+    /// The coordinate where the next lexeme starts.
+    coord: Coord,
+    /// Whether an [`LexemeKind::Eof`] lexeme should be produced at the end.
+    eof: bool,
+    /// Whether the end of the source code was reached and reported.
+    finished: bool,
+    /// The interpolation holes of the strings the next lexeme is in, innermost last.
+    holes: Vec<Hole>,
+    errors: Vec<LexError>,
+}
+
+/// An interpolation hole of a string, where the lexer goes back to lexing code until the `}`
+/// closing it resumes the string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Hole {
+    /// The number of `{` opened inside the hole and not closed yet.
+    depth: u32,
+    /// The byte offset of the `{` opening the hole.
+    start: usize,
+    /// The coordinate of the `{` opening the hole.
+    start_coord: Coord,
+}
+
+impl<'src> Lexer<'src> {
+    /// Creates a lexer at the beginning of `src`.
+    pub fn new(src: &'src str) -> Self {
+        Self {
+            src,
+            pos: 0,
+            coord: Coord { line: 1, col: 1 },
+            eof: false,
+            finished: false,
+            holes: vec![],
+            errors: vec![],
         }
+    }
 
-        if c == '\n' {
-            end_coord.line += 1;
-            end_coord.col = 1;
-        } else {
-            end_coord.col += 1;
+    /// Makes the lexer produce an empty [`LexemeKind::Eof`] lexeme once the end of the source
+    /// code is reached.
+    pub fn with_eof(mut self) -> Self {
+        self.eof = true;
+        self
+    }
+
+    /// The byte offset in the source code where the next lexeme starts.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The coordinate in the source code where the next lexeme starts.
+    pub fn coord(&self) -> Coord {
+        self.coord
+    }
+
+    /// The errors found so far.
+    pub fn errors(&self) -> &[LexError] {
+        &self.errors
+    }
+
+    /// Consumes the lexer returning the errors found so far.
+    pub fn into_errors(self) -> Vec<LexError> {
+        self.errors
+    }
+
+    /// Lexes the next lexeme starting at the current position, skipping and reporting the text
+    /// that can't be lexed on the way.
+    ///
+    /// The lexeme is the longest prefix accepted by some candidate (maximal munch), so once
+    /// every candidate dies the lexer rewinds to the end of that prefix.
+    fn next_lexeme(&mut self) -> Option<Lexeme<'src>> {
+        Dfa::with(|dfa| self.next_lexeme_with(dfa))
+    }
+
+    /// Lexes the next lexeme running the candidates through `dfa`.
+    fn next_lexeme_with(&mut self, dfa: &mut Dfa) -> Option<Lexeme<'src>> {
+        'lexing: while self.pos < self.src.len() {
+            let start = self.pos;
+            let start_coord = self.coord;
+            let mut end_coord = start_coord;
+            // A `}` closing an interpolation hole resumes its string
+            let resumes_str = self.src[start..].starts_with('}')
+                && self.holes.last().is_some_and(|hole| hole.depth == 0);
+            let mut state = if resumes_str {
+                Dfa::STR_CONT
+            } else {
+                Dfa::START
+            };
+            // The end of the longest accepted prefix so far and the kinds accepting it
+            let mut accepted: Option<(usize, Coord, LexemeKindSet)> = None;
+
+            for (offset, c) in self.src[start..].char_indices() {
+                let i = start + offset;
+                let next_state = dfa.next(state, c);
+
+                if next_state != Dfa::DEAD {
+                    state = next_state;
+                    end_coord = next_coord(end_coord, c);
+
+                    let kinds = dfa.accepted(state);
+                    if !kinds.is_empty() {
+                        accepted = Some((i + c.len_utf8(), end_coord, kinds));
+                    }
+
+                    continue;
+                }
+
+                match accepted {
+                    Some((end, end_coord, kinds)) => {
+                        if let Some(lexeme) = self.emit(kinds, start, end, start_coord, end_coord) {
+                            return Some(lexeme);
+                        }
+                    }
+                    None if i > start => self.push_error(
+                        dead_end_error_kind(dfa.candidates(state), self.src, start),
+                        start,
+                        i,
+                        start_coord,
+                        end_coord,
+                    ),
+                    None => {
+                        // `c` can't even start a lexeme
+                        let next_coord = next_coord(end_coord, c);
+                        self.push_error(
+                            LexErrorKind::UnexpectedChar(c),
+                            i,
+                            i + c.len_utf8(),
+                            end_coord,
+                            next_coord,
+                        );
+                    }
+                }
+
+                continue 'lexing;
+            }
+
+            // The end of the source code was reached while lexing. Unterminated strings and
+            // comments are reported as a whole instead of rewinding into their contents.
+            let end = self.src.len();
+            let error = dead_end_error_kind(dfa.candidates(state), self.src, start);
+            let unterminated = matches!(
+                error,
+                LexErrorKind::UnterminatedStr | LexErrorKind::UnterminatedBlockComment
+            );
+
+            match accepted {
+                Some((accepted_end, accepted_coord, kinds))
+                    if accepted_end == end || !unterminated =>
+                {
+                    if let Some(lexeme) =
+                        self.emit(kinds, start, accepted_end, start_coord, accepted_coord)
+                    {
+                        return Some(lexeme);
+                    }
+                }
+                _ => {
+                    if resumes_str {
+                        self.holes.pop();
+                    }
+                    self.push_error(error, start, end, start_coord, end_coord);
+                }
+            }
         }
+
+        None
     }
 
-    lexemes
+    /// Emits the lexeme of the given `kinds` from `start` to `end` and moves past it.
+    ///
+    /// A numeric literal running into an identifier character is not emitted, it is reported as
+    /// malformed instead. Any other numeric literal whose value can't be decoded is emitted but
+    /// reported as well.
+    fn emit(
+        &mut self,
+        kinds: LexemeKindSet,
+        start: usize,
+        end: usize,
+        start_coord: Coord,
+        end_coord: Coord,
+    ) -> Option<Lexeme<'src>> {
+        let lexeme = Lexeme {
+            kind: LexemeAmbiguity::try_from(kinds).expect("an accepted prefix has kinds"),
+            slice: &self.src[start..end],
+            start,
+            end,
+            start_coord,
+            end_coord,
+        };
+
+        if is_numeric_literal(&lexeme) && self.src[end..].starts_with(continues_number) {
+            self.skip_malformed_number(start, end, start_coord, end_coord);
+            return None;
+        }
+
+        if let Err(error) = check_literal(&lexeme) {
+            self.errors.push(error);
+        }
+
+        track_holes(&mut self.holes, &lexeme);
+        self.pos = end;
+        self.coord = end_coord;
+        Some(lexeme)
+    }
+
+    /// Reports every interpolation hole left open at the end of the source code.
+    fn report_unclosed_holes(&mut self) {
+        for hole in std::mem::take(&mut self.holes) {
+            self.errors.push(LexError {
+                kind: LexErrorKind::UnclosedHole,
+                start: hole.start,
+                end: hole.start + 1,
+                start_coord: hole.start_coord,
+                end_coord: next_coord(hole.start_coord, '{'),
+            });
+        }
+    }
+
+    /// Skips the rest of a numeric literal that ran into an identifier character at `end`,
+    /// reporting the whole run as malformed.
+    ///
+    /// If the run is a `0b`, `0o` or `0x` literal with a char that is not a digit of its radix,
+    /// it is reported as an invalid digit instead, pointing at that char.
+    fn skip_malformed_number(
+        &mut self,
+        start: usize,
+        mut end: usize,
+        start_coord: Coord,
+        mut end_coord: Coord,
+    ) {
+        for c in self.src[end..].chars().take_while(|&c| continues_number(c)) {
+            end += c.len_utf8();
+            end_coord = next_coord(end_coord, c);
+        }
+
+        let kind = match invalid_digit(&self.src[start..end]) {
+            Some((offset, digit, radix)) => LexErrorKind::InvalidDigit {
+                digit,
+                radix,
+                at: start + offset,
+            },
+            None => LexErrorKind::MalformedNumber,
+        };
+
+        self.push_error(kind, start, end, start_coord, end_coord);
+    }
+
+    /// Reports an error spanning from `start` to `end` and resumes lexing right after it.
+    fn push_error(
+        &mut self,
+        kind: LexErrorKind,
+        start: usize,
+        end: usize,
+        start_coord: Coord,
+        end_coord: Coord,
+    ) {
+        self.errors.push(LexError {
+            kind,
+            start,
+            end,
+            start_coord,
+            end_coord,
+        });
+        self.pos = end;
+        self.coord = end_coord;
+    }
 }
 
+impl<'src> Iterator for Lexer<'src> {
+    type Item = Lexeme<'src>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        if let Some(lexeme) = self.next_lexeme() {
+            return Some(lexeme);
+        }
+
+        self.report_unclosed_holes();
+        self.finished = true;
+        self.eof.then(|| Lexeme {
+            kind: LexemeAmbiguity::Unambiguous(LexemeKind::Eof),
+            slice: &self.src[self.pos..],
+            start: self.pos,
+            end: self.pos,
+            start_coord: self.coord,
+            end_coord: self.coord,
+        })
+    }
+}
+
+impl FusedIterator for Lexer<'_> {}
+
 /// Removes whitespace lexemes from the lexemes stream.
 pub fn remove_ws<'src>(lexemes: Vec<Vec<Lexeme<'src>>>) -> Vec<Vec<Lexeme<'src>>> {
     lexemes
@@ -77,116 +376,142 @@ pub fn remove_comments<'src>(lexemes: Vec<Vec<Lexeme<'src>>>) -> Vec<Vec<Lexeme<
         .map(|group| {
             group
                 .into_iter()
-                .filter(|lexeme| {
-                    lexeme.kind.unambiguous_unchecked() != LexemeKind::CommentLine
-                        && lexeme.kind.unambiguous_unchecked() != LexemeKind::CommentBlock
-                })
+                .filter(|lexeme| !lexeme.kind.unambiguous_unchecked().is_comment())
                 .collect()
         })
         .filter(|group: &Vec<Lexeme<'src>>| !group.is_empty())
         .collect()
 }
 
-/// Runs `accept` on every candidate in `candidates` and returns the ones that accept `c`.
-#[cfg(not(feature = "parallel"))]
-fn get_next_candidates(candidates: &[LexemeAccepter], c: char) -> Vec<LexemeAccepter> {
-    candidates.iter().filter_map(|s| s.accept(c)).collect()
+/// Keeps track of the interpolation holes opened and closed by `lexeme`, along with the braces
+/// inside them so only the `}` closing a hole resumes its string.
+fn track_holes(holes: &mut Vec<Hole>, lexeme: &Lexeme) {
+    let opened = Hole {
+        depth: 0,
+        start: lexeme.end - 1,
+        start_coord: Coord {
+            line: lexeme.end_coord.line,
+            col: lexeme.end_coord.col - 1,
+        },
+    };
+
+    match lexeme.kind.unambiguous() {
+        Some(LexemeKind::LitStrHead) => holes.push(opened),
+        Some(LexemeKind::LitStrMid) => {
+            if let Some(hole) = holes.last_mut() {
+                *hole = opened;
+            }
+        }
+        Some(LexemeKind::LitStrTail) => {
+            holes.pop();
+        }
+        Some(LexemeKind::DelimOBrace) => {
+            if let Some(hole) = holes.last_mut() {
+                hole.depth += 1;
+            }
+        }
+        Some(LexemeKind::DelimCBrace) => {
+            if let Some(hole) = holes.last_mut() {
+                hole.depth = hole.depth.saturating_sub(1);
+            }
+        }
+        _ => {}
+    }
 }
 
-/// Runs `accept` on every candidate in `candidates` in parallel and returns the ones that accept `c`.
-#[cfg(feature = "parallel")]
-fn get_next_candidates(candidates: &[LexemeAccepter], c: char) -> Vec<LexemeAccepter> {
-    use rayon::prelude::*;
+/// Returns the coordinate following `coord` once `c` is read.
+pub(crate) fn next_coord(mut coord: Coord, c: char) -> Coord {
+    if c == '\n' {
+        coord.line += 1;
+        coord.col = 1;
+    } else {
+        coord.col += 1;
+    }
 
-    candidates
-        .into_par_iter()
-        .filter_map(|s| s.accept(c))
-        .collect()
+    coord
 }
 
-/// Returns the number of acceptable candidates in `candidates`.
-#[cfg(not(feature = "parallel"))]
-fn acceptable_candidates_count(candidates: &[LexemeAccepter]) -> usize {
-    candidates.iter().filter(|s| s.acceptable()).count()
+/// Checks if `c` would be read as part of a numeric literal, even if it makes it invalid.
+fn continues_number(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
 }
 
-/// Returns the number of acceptable candidates in `candidates` in parallel.
-#[cfg(feature = "parallel")]
-fn acceptable_candidates_count(candidates: &[LexemeAccepter]) -> usize {
-    use rayon::prelude::*;
+/// Finds the first char of the digits of a `0b`, `0o` or `0x` literal that is not a digit of its
+/// radix, returning its offset in `run`, the char itself and the radix.
+///
+/// The digits end where a `U` or `I` width suffix starts.
+fn invalid_digit(run: &str) -> Option<(usize, char, u32)> {
+    let radix = match run.get(..2)? {
+        "0b" => 2,
+        "0o" => 8,
+        "0x" => 16,
+        _ => return None,
+    };
 
-    candidates.par_iter().filter(|s| s.acceptable()).count()
+    run[2..]
+        .char_indices()
+        .take_while(|&(_, c)| c != 'U' && c != 'I')
+        .find(|&(_, c)| c != '_' && !c.is_digit(radix))
+        .map(|(i, c)| (i + 2, c, radix))
 }
 
-/// Builds lexemes from the candidates in `candidates` and returns them.
-#[cfg(not(feature = "parallel"))]
-fn build_lexeme_from_candidates(
-    candidates: Vec<LexemeAccepter>,
-    src: &str,
-    start: usize,
-    end: usize,
-    start_coord: Coord,
-    end_coord: Coord,
-) -> Lexeme<'_> {
-    use crate::lexeme::LexemeAmbiguity;
-
-    let states: Vec<_> = candidates.into_iter().filter(|s| s.acceptable()).collect();
-
-    let kind = if states.len() == 1 {
-        LexemeAmbiguity::Unambiguous(states[0].into())
-    } else {
-        let (a, b) = (states[0].into(), states[1].into());
-        LexemeAmbiguity::Ambiguous(a, b)
-    };
+/// Checks if `lexeme` is an integer or floating point literal.
+fn is_numeric_literal(lexeme: &Lexeme) -> bool {
+    lexeme
+        .kind
+        .unambiguous()
+        .is_some_and(LexemeKind::is_numeric_literal)
+}
 
-    Lexeme {
-        kind,
-        slice: &src[start..end],
-        start,
-        end,
-        start_coord,
-        end_coord,
+/// Decodes the value of literal lexemes so the invalid ones can be reported.
+fn check_literal(lexeme: &Lexeme) -> Result<(), LexError> {
+    match lexeme.kind.unambiguous() {
+        Some(
+            LexemeKind::LitIntDec
+            | LexemeKind::LitIntBin
+            | LexemeKind::LitIntOct
+            | LexemeKind::LitIntHex,
+        ) => lexeme.int_value().map(drop),
+        Some(LexemeKind::LitFlt) => lexeme.flt_value().map(drop),
+        Some(
+            LexemeKind::LitStr
+            | LexemeKind::LitStrMulti
+            | LexemeKind::LitStrHead
+            | LexemeKind::LitStrMid
+            | LexemeKind::LitStrTail,
+        ) => lexeme.check_str_value(),
+        Some(LexemeKind::LitChr) => lexeme.chr_value().map(drop),
+        _ => Ok(()),
     }
 }
 
-/// Builds lexemes from the candidates in `candidates` in parallel and returns them.
-#[cfg(feature = "parallel")]
-fn build_lexeme_from_candidates(
-    candidates: Vec<LexemeAccepter>,
-    src: &str,
-    start: usize,
-    end: usize,
-    start_coord: Coord,
-    end_coord: Coord,
-) -> Lexeme<'_> {
-    use crate::lexeme::LexemeAmbiguity;
-    use rayon::prelude::*;
-
-    let states: Vec<_> = candidates
-        .into_par_iter()
-        .filter(|s| s.acceptable())
-        .collect();
-
-    let kind = if states.len() == 1 {
-        LexemeAmbiguity::Unambiguous(states[0].into())
-    } else {
-        let (a, b) = (states[0].into(), states[1].into());
-        LexemeAmbiguity::Ambiguous(a, b)
+/// Figures out why the text starting at `start` couldn't become a lexeme from the `candidates`
+/// that were still being considered when the lexing got stuck.
+fn dead_end_error_kind(candidates: &[LexemeAccepter], src: &str, start: usize) -> LexErrorKind {
+    let kinds = || candidates.iter().map(|&acp| LexemeKind::from(acp));
+    // A raw string isn't a string until its opening `"`, so `r#` rewinds like any dead prefix
+    let in_str = |&acp: &LexemeAccepter| match acp {
+        LexemeAccepter::Lit(LitAccepter::StrRaw(raw)) => raw.is_open(),
+        acp => LexemeKind::from(acp).is_str_literal(),
     };
 
-    Lexeme {
-        kind,
-        slice: &src[start..end],
-        start,
-        end,
-        start_coord,
-        end_coord,
+    if candidates.iter().any(in_str) {
+        LexErrorKind::UnterminatedStr
+    } else if kinds()
+        .any(|kind| kind == LexemeKind::CommentBlock || kind == LexemeKind::CommentDocBlock)
+    {
+        LexErrorKind::UnterminatedBlockComment
+    } else if kinds().any(LexemeKind::is_numeric_literal) {
+        LexErrorKind::MalformedNumber
+    } else {
+        let c = src[start..].chars().next().unwrap_or_default();
+        LexErrorKind::UnexpectedChar(c)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::lexeme::kind::LexemeKind;
+    use crate::lexeme::{accepter::Accepter, kind::LexemeKind};
 
     use super::*;
 
@@ -194,7 +519,7 @@ mod tests {
     fn lexeme_test() {
         let src = "val   x:=10     ;// line comment
         ";
-        let chars = src.chars().enumerate();
+        let chars = src.char_indices();
         let mut start = 0;
 
         let mut candidates = LexemeAccepter::stream();
@@ -244,10 +569,10 @@ mod tests {
         "#;
         let lexemes = lex(src);
 
-        assert_eq!(lexemes.len(), 77);
+        assert_eq!(lexemes.len(), 78);
         assert_eq!(
             lexemes[0].kind.ambiguous_unchecked(),
-            (LexemeKind::KwVal, LexemeKind::IdentVal)
+            LexemeKindSet::from([LexemeKind::KwVal, LexemeKind::IdentVal])
         );
         assert_eq!(lexemes[0].slice, "val");
         assert_eq!(lexemes[1].kind.unambiguous_unchecked(), LexemeKind::Ws);
@@ -267,7 +592,7 @@ mod tests {
         assert_eq!(lexemes[7].kind.unambiguous_unchecked(), LexemeKind::Ws);
         assert_eq!(
             lexemes[8].kind.ambiguous_unchecked(),
-            (LexemeKind::KwVal, LexemeKind::IdentVal)
+            LexemeKindSet::from([LexemeKind::KwVal, LexemeKind::IdentVal])
         );
         assert_eq!(lexemes[8].slice, "val");
         assert_eq!(lexemes[9].kind.unambiguous_unchecked(), LexemeKind::Ws);
@@ -284,7 +609,7 @@ mod tests {
         assert_eq!(lexemes[15].kind.unambiguous_unchecked(), LexemeKind::Ws);
         assert_eq!(
             lexemes[16].kind.ambiguous_unchecked(),
-            (LexemeKind::KwType, LexemeKind::IdentVal)
+            LexemeKindSet::from([LexemeKind::KwType, LexemeKind::IdentVal])
         );
         assert_eq!(lexemes[16].slice, "type");
         assert_eq!(lexemes[17].kind.unambiguous_unchecked(), LexemeKind::Ws);
@@ -370,10 +695,395 @@ mod tests {
         assert_eq!(lexemes[43].kind.unambiguous_unchecked(), LexemeKind::Ws);
         assert_eq!(
             lexemes[44].kind.ambiguous_unchecked(),
-            (LexemeKind::KwMain, LexemeKind::IdentVal)
+            LexemeKindSet::from([LexemeKind::KwMain, LexemeKind::IdentVal])
         );
         assert_eq!(lexemes[44].slice, "main");
         assert_eq!(lexemes[45].kind.unambiguous_unchecked(), LexemeKind::Ws);
         assert_eq!(lexemes[46].kind.unambiguous_unchecked(), LexemeKind::OpRArw);
     }
+
+    #[test]
+    fn test_lex_errors() {
+        use super::error::LexErrorKind;
+
+        let (lexemes, errors) = lex_with_errors("x ? y");
+        assert_eq!(lexemes.len(), 4);
+        assert_eq!(lexemes[2].slice, " ");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, LexErrorKind::UnexpectedChar('?'));
+        assert_eq!((errors[0].start, errors[0].end), (2, 3));
+        assert_eq!(errors[0].start_coord, Coord { line: 1, col: 3 });
+
+        let (_, errors) = lex_with_errors("x := 12abc");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, LexErrorKind::MalformedNumber);
+        assert_eq!((errors[0].start, errors[0].end), (5, 10));
+
+        let (_, errors) = lex_with_errors("x := 1U");
+        assert_eq!(errors[0].kind, LexErrorKind::MalformedNumber);
+        assert_eq!((errors[0].start, errors[0].end), (5, 7));
+
+        let (_, errors) = lex_with_errors("x := 0xZZTOP");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].kind,
+            LexErrorKind::InvalidDigit {
+                digit: 'Z',
+                radix: 16,
+                at: 7
+            }
+        );
+        assert_eq!((errors[0].start, errors[0].end), (5, 12));
+        assert_eq!(errors[0].start_coord, Coord { line: 1, col: 6 });
+
+        let (lexemes, errors) = lex_with_errors("0b102 0o78 0xFFU9");
+        assert_eq!(lexemes.len(), 2);
+        let kinds: Vec<_> = errors.iter().map(|error| error.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                LexErrorKind::InvalidDigit {
+                    digit: '2',
+                    radix: 2,
+                    at: 4
+                },
+                LexErrorKind::InvalidDigit {
+                    digit: '8',
+                    radix: 8,
+                    at: 9
+                },
+                LexErrorKind::MalformedNumber,
+            ]
+        );
+        assert_eq!((errors[0].start, errors[0].end), (0, 5));
+
+        let (_, errors) = lex_with_errors("x := 1.5e+");
+        assert_eq!(errors[0].kind, LexErrorKind::MalformedNumber);
+        assert_eq!((errors[0].start, errors[0].end), (5, 9));
+
+        let (_, errors) = lex_with_errors("x := \"abc\n");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, LexErrorKind::UnterminatedStr);
+        assert_eq!(errors[0].start_coord, Coord { line: 1, col: 6 });
+        assert_eq!(errors[0].end_coord, Coord { line: 2, col: 1 });
+
+        let (_, errors) = lex_with_errors("x /* abc");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, LexErrorKind::UnterminatedBlockComment);
+        assert_eq!((errors[0].start, errors[0].end), (2, 8));
+    }
+
+    #[test]
+    fn test_lex_end_of_input() {
+        let lexemes = lex("x := 10");
+        assert_eq!(lexemes.len(), 5);
+        assert_eq!(
+            lexemes[4].kind.unambiguous_unchecked(),
+            LexemeKind::LitIntDec
+        );
+        assert_eq!(lexemes[4].slice, "10");
+        assert_eq!((lexemes[4].start, lexemes[4].end), (5, 7));
+
+        let lexemes = lex_with_eof("x := 10\n");
+        assert_eq!(lexemes.len(), 7);
+        assert_eq!(lexemes[5].kind.unambiguous_unchecked(), LexemeKind::Ws);
+        assert_eq!(lexemes[6].kind.unambiguous_unchecked(), LexemeKind::Eof);
+        assert_eq!(lexemes[6].slice, "");
+        assert_eq!((lexemes[6].start, lexemes[6].end), (8, 8));
+        assert_eq!(lexemes[6].start_coord, Coord { line: 2, col: 1 });
+
+        let lexemes = lex_with_eof("");
+        assert_eq!(lexemes.len(), 1);
+        assert_eq!(lexemes[0].start_coord, Coord { line: 1, col: 1 });
+    }
+
+    #[test]
+    fn test_lexer_on_demand() {
+        let src = "val x := \"never closed";
+        let mut lexer = Lexer::new(src);
+
+        assert_eq!(lexer.next().map(|lexeme| lexeme.slice), Some("val"));
+        assert_eq!(lexer.next().map(|lexeme| lexeme.slice), Some(" "));
+        assert_eq!(lexer.position(), 4);
+        assert_eq!(lexer.coord(), Coord { line: 1, col: 5 });
+        assert!(lexer.errors().is_empty());
+
+        assert_eq!(lexer.by_ref().count(), 4);
+        assert_eq!(lexer.position(), src.len());
+        assert_eq!(lexer.errors().len(), 1);
+        assert_eq!(lexer.next(), None);
+
+        let mut lexer = Lexer::new("x").with_eof();
+        assert_eq!(lexer.next().map(|lexeme| lexeme.slice), Some("x"));
+        assert_eq!(
+            lexer
+                .next()
+                .map(|lexeme| lexeme.kind.unambiguous_unchecked()),
+            Some(LexemeKind::Eof)
+        );
+        assert_eq!(lexer.next(), None);
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn test_lex_maximal_munch() {
+        let kinds = |src| {
+            lex(src)
+                .into_iter()
+                .map(|lexeme| (lexeme.kind.unambiguous_unchecked(), lexeme.slice))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            kinds("1..5"),
+            [
+                (LexemeKind::LitIntDec, "1"),
+                (LexemeKind::OpRange, ".."),
+                (LexemeKind::LitIntDec, "5"),
+            ]
+        );
+        assert_eq!(
+            kinds("1.x"),
+            [
+                (LexemeKind::LitIntDec, "1"),
+                (LexemeKind::PtDot, "."),
+                (LexemeKind::IdentVal, "x"),
+            ]
+        );
+        assert_eq!(kinds("1.5"), [(LexemeKind::LitFlt, "1.5")]);
+        assert_eq!(kinds("6.02e-23F64"), [(LexemeKind::LitFlt, "6.02e-23F64")]);
+        assert_eq!(kinds("1_000.5F32"), [(LexemeKind::LitFlt, "1_000.5F32")]);
+        assert_eq!(kinds("0b1010"), [(LexemeKind::LitIntBin, "0b1010")]);
+        assert_eq!(kinds("0xFF_FFU16"), [(LexemeKind::LitIntHex, "0xFF_FFU16")]);
+        assert_eq!(kinds("0b1010I16"), [(LexemeKind::LitIntBin, "0b1010I16")]);
+        assert_eq!(kinds("0o777U64"), [(LexemeKind::LitIntOct, "0o777U64")]);
+        assert_eq!(
+            kinds("a..=b"),
+            [
+                (LexemeKind::IdentVal, "a"),
+                (LexemeKind::OpCRange, "..="),
+                (LexemeKind::IdentVal, "b"),
+            ]
+        );
+        assert_eq!(
+            kinds("a/*b*/"),
+            [
+                (LexemeKind::IdentVal, "a"),
+                (LexemeKind::CommentBlock, "/*b*/"),
+            ]
+        );
+        assert_eq!(
+            kinds("a/ b"),
+            [
+                (LexemeKind::IdentVal, "a"),
+                (LexemeKind::OpSlash, "/"),
+                (LexemeKind::Ws, " "),
+                (LexemeKind::IdentVal, "b"),
+            ]
+        );
+
+        let (lexemes, errors) = lex_with_errors("0x ");
+        assert_eq!(lexemes.len(), 1);
+        assert_eq!(errors[0].kind, LexErrorKind::MalformedNumber);
+        assert_eq!((errors[0].start, errors[0].end), (0, 2));
+    }
+
+    #[test]
+    fn test_lex_int_overflow() {
+        let (lexemes, errors) = lex_with_errors("x := 300U8");
+
+        assert_eq!(lexemes.len(), 5);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0].kind, LexErrorKind::IntOverflow(Some(_))));
+        assert_eq!((errors[0].start, errors[0].end), (5, 10));
+    }
+
+    #[test]
+    fn test_lex_nested_comments() {
+        let src = "a /* x /* y */ z */ b";
+        let lexemes = lex(src);
+        assert_eq!(lexemes.len(), 5);
+        assert_eq!(lexemes[2].slice, "/* x /* y */ z */");
+        assert_eq!(
+            lexemes[2].kind,
+            LexemeAmbiguity::Unambiguous(LexemeKind::CommentBlock)
+        );
+        assert_eq!(lex("/**/ /*/**/*/ /***/").len(), 5);
+
+        let deep = "/*".repeat(10_000) + &"*/".repeat(10_000);
+        assert_eq!(lex(&deep)[0].slice, deep);
+
+        let (lexemes, errors) = lex_with_errors("a\n  /* x /* y */ z");
+        assert_eq!(lexemes.len(), 2);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, LexErrorKind::UnterminatedBlockComment);
+        assert_eq!(errors[0].start, 4);
+        assert_eq!(errors[0].start_coord, Coord { line: 2, col: 3 });
+    }
+
+    #[test]
+    fn test_lex_doc_comments() {
+        let kinds = |src| {
+            lex(src)
+                .into_iter()
+                .map(|lexeme| lexeme.kind.unambiguous().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            kinds("/// a\n//! b\n//// c\n// d"),
+            [
+                LexemeKind::CommentDocOuter,
+                LexemeKind::Ws,
+                LexemeKind::CommentDocInner,
+                LexemeKind::Ws,
+                LexemeKind::CommentLine,
+                LexemeKind::Ws,
+                LexemeKind::CommentLine,
+            ]
+        );
+        assert_eq!(
+            kinds("/** a /* b */ */ /**/ /*** c */ /* d */"),
+            [
+                LexemeKind::CommentDocBlock,
+                LexemeKind::Ws,
+                LexemeKind::CommentBlock,
+                LexemeKind::Ws,
+                LexemeKind::CommentBlock,
+                LexemeKind::Ws,
+                LexemeKind::CommentBlock,
+            ]
+        );
+
+        let (_, errors) = lex_with_errors("/** a");
+        assert_eq!(errors[0].kind, LexErrorKind::UnterminatedBlockComment);
+    }
+
+    #[test]
+    fn test_lex_raw_strings() {
+        let (lexemes, errors) = lex_with_errors(r###"r"\d+" r##"a "# b"## r"###);
+        let kinds: Vec<_> = lexemes
+            .iter()
+            .map(|lexeme| (lexeme.kind.unambiguous().unwrap(), lexeme.slice))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            [
+                (LexemeKind::LitStrRaw, r#"r"\d+""#),
+                (LexemeKind::Ws, " "),
+                (LexemeKind::LitStrRaw, r###"r##"a "# b"##"###),
+                (LexemeKind::Ws, " "),
+                (LexemeKind::IdentVal, "r"),
+            ]
+        );
+        assert!(errors.is_empty());
+
+        let (_, errors) = lex_with_errors("x := r#\"abc\"");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, LexErrorKind::UnterminatedStr);
+        assert_eq!((errors[0].start, errors[0].end), (5, 12));
+
+        // Without its opening `"` a raw string is just an `r` at the end too
+        for src in ["r#", "x r#\n"] {
+            let (lexemes, errors) = lex_with_errors(src);
+            let r = lexemes.iter().find(|lexeme| lexeme.slice == "r").unwrap();
+            assert_eq!(r.kind.unambiguous(), Some(LexemeKind::IdentVal));
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].kind, LexErrorKind::UnexpectedChar('#'));
+        }
+    }
+
+    #[test]
+    fn test_lex_multi_line_strings() {
+        let src = "s := \"\"\"\n  a \"\" b\n  \"\"\" ;\nt := \"\"";
+        let lexemes = lex(src);
+
+        assert_eq!(lexemes[4].slice, "\"\"\"\n  a \"\" b\n  \"\"\"");
+        assert_eq!(
+            lexemes[4].kind,
+            LexemeAmbiguity::Unambiguous(LexemeKind::LitStrMulti)
+        );
+        assert_eq!(lexemes[4].start_coord, Coord { line: 1, col: 6 });
+        assert_eq!(lexemes[4].end_coord, Coord { line: 3, col: 6 });
+        assert_eq!(lexemes[6].start_coord, Coord { line: 3, col: 7 });
+        assert_eq!(
+            lexemes[12].kind,
+            LexemeAmbiguity::Unambiguous(LexemeKind::LitStr)
+        );
+
+        let (_, errors) = lex_with_errors("s := \"\"\"\nabc\"\"");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, LexErrorKind::UnterminatedStr);
+        assert_eq!((errors[0].start, errors[0].end), (5, 14));
+    }
+
+    #[test]
+    fn test_lex_interpolation() {
+        let kinds = |src| {
+            lex(src)
+                .into_iter()
+                .filter(|lexeme| lexeme.kind.unambiguous() != Some(LexemeKind::Ws))
+                .map(|lexeme| (lexeme.kind.unambiguous().unwrap(), lexeme.slice))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            kinds(r#""Hello {name}, you are {age + 1}""#),
+            [
+                (LexemeKind::LitStrHead, r#""Hello {"#),
+                (LexemeKind::IdentVal, "name"),
+                (LexemeKind::LitStrMid, "}, you are {"),
+                (LexemeKind::IdentVal, "age"),
+                (LexemeKind::OpPlus, "+"),
+                (LexemeKind::LitIntDec, "1"),
+                (LexemeKind::LitStrTail, r#"}""#),
+            ]
+        );
+        assert_eq!(
+            kinds(r#""a {f({x}, "b {y}")} \{c}" }"#),
+            [
+                (LexemeKind::LitStrHead, r#""a {"#),
+                (LexemeKind::IdentVal, "f"),
+                (LexemeKind::DelimOParen, "("),
+                (LexemeKind::DelimOBrace, "{"),
+                (LexemeKind::IdentVal, "x"),
+                (LexemeKind::DelimCBrace, "}"),
+                (LexemeKind::PtComma, ","),
+                (LexemeKind::LitStrHead, r#""b {"#),
+                (LexemeKind::IdentVal, "y"),
+                (LexemeKind::LitStrTail, r#"}""#),
+                (LexemeKind::DelimCParen, ")"),
+                (LexemeKind::LitStrTail, r#"} \{c}""#),
+                (LexemeKind::DelimCBrace, "}"),
+            ]
+        );
+
+        let (lexemes, errors) = lex_with_errors("s := \"a {x\ny\"");
+        assert_eq!(
+            lexemes[4].kind,
+            LexemeAmbiguity::Unambiguous(LexemeKind::LitStrHead)
+        );
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].kind, LexErrorKind::UnterminatedStr);
+        assert_eq!(errors[1].kind, LexErrorKind::UnclosedHole);
+        assert_eq!((errors[1].start, errors[1].end), (8, 9));
+        assert_eq!(errors[1].start_coord, Coord { line: 1, col: 9 });
+
+        let (_, errors) = lex_with_errors("\"a {x} b");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, LexErrorKind::UnterminatedStr);
+        assert_eq!((errors[0].start, errors[0].end), (5, 8));
+    }
+
+    #[test]
+    fn test_lex_unknown_escape() {
+        let (lexemes, errors) = lex_with_errors(r#"s := "a\qb""#);
+
+        assert_eq!(lexemes.len(), 5);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, LexErrorKind::UnknownEscape('q'));
+        assert_eq!((errors[0].start, errors[0].end), (7, 9));
+        assert_eq!(errors[0].start_coord, Coord { line: 1, col: 8 });
+    }
 }
@@ -0,0 +1,333 @@
+use std::{iter::FusedIterator, ops::Range};
+
+use super::{next_coord, Lexer};
+use crate::lexeme::{
+    kind::{LexemeKind, LexemeKindSet},
+    Coord, Lexeme, LexemeAmbiguity,
+};
+
+/// The lexemes of a source code stored compactly, taking 9 bytes per lexeme instead of a whole
+/// [`Lexeme`].
+///
+/// Kinds are stored as bytes, the ambiguous ones as the index of their kinds among the few
+/// distinct ambiguous kinds, and spans as 32 bits start and length. Coordinates are not stored at
+/// all, they are computed on demand from the offsets of the line starts, so they cost 4 bytes per
+/// line instead, plus 8 bytes per KiB of long lines to find columns quickly.
+///
+/// The lexemes can still be handed out as [`Lexeme`]s, built as they are needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenBuffer<'src> {
+    src: &'src str,
+    kinds: Vec<u8>,
+    starts: Vec<u32>,
+    lens: Vec<u32>,
+    /// The distinct kinds of the ambiguous lexemes, such as each keyword along with a value
+    /// identifier.
+    kind_sets: Vec<LexemeKindSet>,
+    /// The byte offsets where each line starts, the first line included.
+    line_starts: Vec<u32>,
+    /// The byte offsets and columns of chars every [`CHECKPOINT_SPACING`] bytes or so within lines,
+    /// so columns are counted from the closest one.
+    checkpoints: Vec<(u32, u32)>,
+}
+
+/// The first byte standing for an ambiguous kind, which is no [`LexemeKind`]. The byte minus this
+/// one is the index of its kinds in [`TokenBuffer::kind_sets`].
+const AMBIGUOUS: u8 = 128;
+
+const _: () = assert!(LexemeKind::ALL.len() <= AMBIGUOUS as usize);
+
+/// The least number of bytes between a line start or a column checkpoint and the next checkpoint.
+const CHECKPOINT_SPACING: usize = 1024;
+
+/// Lexes `src` into a [`TokenBuffer`], skipping the text that can't be lexed just like
+/// [`lex`](super::lex).
+///
+/// # Panics
+///
+/// Panics if `src` is 4 GiB long or more, as offsets wouldn't fit in 32 bits.
+pub fn lex_to_buffer(src: &str) -> TokenBuffer<'_> {
+    let offset = |offset: usize| u32::try_from(offset).expect("the source code is under 4 GiB");
+
+    let mut buffer = TokenBuffer {
+        src,
+        kinds: vec![],
+        starts: vec![],
+        lens: vec![],
+        kind_sets: vec![],
+        line_starts: vec![0],
+        checkpoints: vec![],
+    };
+
+    // The offset of the last line start or checkpoint, and the column of the char at `i`
+    let mut last = 0;
+    let mut col = 1;
+    for (i, c) in src.char_indices() {
+        if i - last >= CHECKPOINT_SPACING {
+            buffer.checkpoints.push((offset(i), offset(col)));
+            last = i;
+        }
+
+        if c == '\n' {
+            buffer.line_starts.push(offset(i + 1));
+            last = i + 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
+    }
+
+    for lexeme in Lexer::new(src) {
+        let kind = match lexeme.kind {
+            LexemeAmbiguity::Unambiguous(kind) => kind as u8,
+            LexemeAmbiguity::Ambiguous(kinds) => {
+                let index = match buffer.kind_sets.iter().position(|&set| set == kinds) {
+                    Some(index) => index,
+                    None => {
+                        buffer.kind_sets.push(kinds);
+                        buffer.kind_sets.len() - 1
+                    }
+                };
+                u8::try_from(AMBIGUOUS as usize + index)
+                    .expect("there are few distinct ambiguous kinds")
+            }
+        };
+
+        buffer.kinds.push(kind);
+        buffer.starts.push(offset(lexeme.start));
+        buffer.lens.push(offset(lexeme.end - lexeme.start));
+    }
+
+    buffer
+}
+
+impl<'src> TokenBuffer<'src> {
+    /// The source code the lexemes were lexed from.
+    pub fn src(&self) -> &'src str {
+        self.src
+    }
+
+    /// The number of lexemes.
+    pub fn len(&self) -> usize {
+        self.kinds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    /// The kind of the `i`-th lexeme.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds, as do the other accessors taking an index.
+    pub fn kind(&self, i: usize) -> LexemeAmbiguity {
+        match self.kinds[i] {
+            kind if kind >= AMBIGUOUS => {
+                LexemeAmbiguity::Ambiguous(self.kind_sets[(kind - AMBIGUOUS) as usize])
+            }
+            kind => LexemeAmbiguity::Unambiguous(
+                LexemeKind::try_from(kind).expect("kinds are stored as their discriminant"),
+            ),
+        }
+    }
+
+    /// The byte span of the `i`-th lexeme.
+    pub fn span(&self, i: usize) -> Range<usize> {
+        let start = self.starts[i] as usize;
+        start..start + self.lens[i] as usize
+    }
+
+    /// The slice of the `i`-th lexeme.
+    pub fn slice(&self, i: usize) -> &'src str {
+        &self.src[self.span(i)]
+    }
+
+    /// The `i`-th lexeme, or `None` if out of bounds.
+    pub fn get(&self, i: usize) -> Option<Lexeme<'src>> {
+        if i >= self.len() {
+            return None;
+        }
+
+        let span = self.span(i);
+        Some(Lexeme {
+            kind: self.kind(i),
+            slice: &self.src[span.clone()],
+            start: span.start,
+            end: span.end,
+            start_coord: self.coord_at(span.start),
+            end_coord: self.coord_at(span.end),
+        })
+    }
+
+    /// The coordinate of the byte `offset` in the source code.
+    ///
+    /// Finding the line takes a binary search, and the column another one for the closest
+    /// checkpoint followed by a count of the chars between it and `offset`, a KiB at most.
+    pub fn coord_at(&self, offset: usize) -> Coord {
+        let line = self
+            .line_starts
+            .partition_point(|&start| start as usize <= offset);
+        let line_start = self.line_starts[line - 1] as usize;
+
+        let (from, col) = match self
+            .checkpoints
+            .partition_point(|&(at, _)| at as usize <= offset)
+        {
+            0 => (line_start, 1),
+            i => {
+                let (at, col) = self.checkpoints[i - 1];
+                if at as usize >= line_start {
+                    (at as usize, col as usize)
+                } else {
+                    (line_start, 1)
+                }
+            }
+        };
+
+        Coord {
+            line,
+            col: col + self.src[from..offset].chars().count(),
+        }
+    }
+
+    /// Iterates over the lexemes, computing their coordinates as it goes instead of searching for
+    /// them.
+    pub fn iter(&self) -> Iter<'_, 'src> {
+        Iter {
+            buffer: self,
+            index: 0,
+            offset: 0,
+            coord: Coord { line: 1, col: 1 },
+        }
+    }
+}
+
+impl<'a, 'src> IntoIterator for &'a TokenBuffer<'src> {
+    type Item = Lexeme<'src>;
+    type IntoIter = Iter<'a, 'src>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the lexemes of a [`TokenBuffer`].
+#[derive(Debug, Clone)]
+pub struct Iter<'a, 'src> {
+    buffer: &'a TokenBuffer<'src>,
+    index: usize,
+    /// The byte offset `coord` is the coordinate of.
+    offset: usize,
+    coord: Coord,
+}
+
+impl Iter<'_, '_> {
+    /// Moves the coordinate forward to the byte `offset`.
+    fn advance_to(&mut self, offset: usize) -> Coord {
+        self.coord = self.buffer.src[self.offset..offset]
+            .chars()
+            .fold(self.coord, next_coord);
+        self.offset = offset;
+        self.coord
+    }
+}
+
+impl<'src> Iterator for Iter<'_, 'src> {
+    type Item = Lexeme<'src>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.index;
+        if i >= self.buffer.len() {
+            return None;
+        }
+        self.index += 1;
+
+        let span = self.buffer.span(i);
+        Some(Lexeme {
+            kind: self.buffer.kind(i),
+            slice: &self.buffer.src[span.clone()],
+            start: span.start,
+            end: span.end,
+            start_coord: self.advance_to(span.start),
+            end_coord: self.advance_to(span.end),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.buffer.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_, '_> {}
+
+impl FusedIterator for Iter<'_, '_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lex;
+
+    const SRC: &str = "val café := \"crème\\n\" // → ok\r\ntype :=\n\n  '😀' 0xFFU8\n";
+
+    #[test]
+    fn buffer_matches_lex() {
+        let lexemes = lex(SRC);
+        let buffer = lex_to_buffer(SRC);
+
+        assert_eq!(buffer.len(), lexemes.len());
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), lexemes);
+        for (i, lexeme) in lexemes.iter().enumerate() {
+            assert_eq!(buffer.get(i).as_ref(), Some(lexeme));
+            assert_eq!(buffer.slice(i), lexeme.slice);
+        }
+        assert_eq!(buffer.get(lexemes.len()), None);
+    }
+
+    #[test]
+    fn buffer_keeps_ambiguous_kinds() {
+        let buffer = lex_to_buffer("val x := type");
+
+        assert_eq!(buffer.kind(0), lex("val")[0].kind);
+        assert!(matches!(buffer.kind(0), LexemeAmbiguity::Ambiguous(_)));
+        assert_eq!(
+            buffer.kind(2),
+            LexemeAmbiguity::Unambiguous(LexemeKind::IdentVal)
+        );
+        assert_eq!(buffer.kind(6), lex("type")[0].kind);
+
+        let buffer = lex_to_buffer("val fn val type fn");
+        assert_eq!(buffer.kind_sets.len(), 3);
+        assert_eq!(buffer.kind(0), buffer.kind(4));
+        assert_ne!(buffer.kind(0), buffer.kind(2));
+    }
+
+    #[test]
+    fn coords_on_demand() {
+        let buffer = lex_to_buffer(SRC);
+
+        assert_eq!(buffer.coord_at(0), Coord { line: 1, col: 1 });
+        assert_eq!(buffer.coord_at(7), Coord { line: 1, col: 8 });
+        assert_eq!(buffer.coord_at(SRC.len()), Coord { line: 5, col: 1 });
+        assert_eq!(
+            buffer.coord_at(SRC.find('😀').unwrap()),
+            Coord { line: 4, col: 4 }
+        );
+    }
+
+    #[test]
+    fn coords_in_long_lines() {
+        let src = format!("{}\n{} é\nx", "é".repeat(3000), "ab".repeat(1500));
+        let buffer = lex_to_buffer(&src);
+
+        assert!(!buffer.checkpoints.is_empty());
+        let mut coord = Coord { line: 1, col: 1 };
+        for (i, c) in src.char_indices() {
+            assert_eq!(buffer.coord_at(i), coord);
+            coord = next_coord(coord, c);
+        }
+        assert_eq!(buffer.coord_at(src.len()), coord);
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), lex(&src));
+    }
+}
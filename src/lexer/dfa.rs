@@ -0,0 +1,318 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use crate::lexeme::{
+    accepter::{
+        comment::CommentAccepter,
+        lit::{LitAccepter, StrContAccepter},
+        Accepter, LexemeAccepter,
+    },
+    kind::{LexemeKind, LexemeKindSet},
+};
+
+/// The identifier of a DFA state, it is its index in [`Dfa::states`].
+pub(crate) type StateId = u32;
+
+/// A transition that wasn't computed yet.
+const UNKNOWN: StateId = StateId::MAX;
+
+/// The most states cached by a DFA. Candidates carrying counters, like the depth of a nested
+/// comment, make for endless states, so past this many the new ones are not cached.
+const MAX_STATES: usize = 4096;
+
+/// The deepest nesting of block comments cached by a DFA. Each level makes new states, and deeper
+/// ones are rare enough to be stepped through without caching them.
+const MAX_CACHED_DEPTH: u32 = 16;
+
+/// The most `#` delimiting a raw string cached by a DFA, for the same reason as
+/// [`MAX_CACHED_DEPTH`].
+const MAX_CACHED_HASHES: u32 = 16;
+
+thread_local! {
+    /// The DFA shared by every lexer in the thread, so the states computed by one lexer are
+    /// reused by the next ones.
+    static DFA: RefCell<Dfa> = RefCell::new(Dfa::new());
+}
+
+/// A lazily built DFA equivalent to running every [`LexemeAccepter`] in parallel.
+///
+/// Each state is a set of candidates, the accepters that accept the text read so far, just like
+/// the candidate vectors of a naive lexer. The states and their transitions are computed the first
+/// time they are needed and cached, so once the DFA is warm lexing a char is a table lookup that
+/// doesn't allocate: ASCII chars index a per state transition table and any other char is looked up
+/// in a per state map.
+///
+/// Once [`MAX_STATES`] are cached, or for the states of comments nested deeper than
+/// [`MAX_CACHED_DEPTH`] and raw strings with more than [`MAX_CACHED_HASHES`], a new state is kept
+/// aside as the single [`Dfa::UNCACHED`] state instead, which is stepped through by running its
+/// candidates like a naive lexer would.
+#[derive(Debug)]
+pub(crate) struct Dfa {
+    states: Vec<State>,
+    ids: HashMap<Box<[LexemeAccepter]>, StateId>,
+    /// The most states cached, [`MAX_STATES`] unless testing.
+    max_states: usize,
+    /// The state reached last that is not cached.
+    uncached: State,
+}
+
+#[derive(Debug)]
+struct State {
+    candidates: Box<[LexemeAccepter]>,
+    /// The kinds of the candidates accepting the text read so far.
+    accepted: LexemeKindSet,
+    ascii: [StateId; 128],
+    other: HashMap<char, StateId>,
+}
+
+impl Dfa {
+    /// The state without candidates, once reached no lexeme can be accepted anymore.
+    pub(crate) const DEAD: StateId = 0;
+    /// The state before any char is read, where every accepter is a candidate.
+    pub(crate) const START: StateId = 1;
+    /// The state before any char is read where a string resumes after an interpolation hole,
+    /// whose only candidate is the rest of the string.
+    pub(crate) const STR_CONT: StateId = 2;
+    /// The state reached last if it is not cached. It stands for a different state every time a
+    /// state that is not cached is reached.
+    pub(crate) const UNCACHED: StateId = StateId::MAX - 1;
+
+    fn new() -> Self {
+        let mut dfa = Self {
+            states: vec![],
+            ids: HashMap::new(),
+            max_states: MAX_STATES,
+            uncached: State::new(Box::new([])),
+        };
+        dfa.intern(vec![]);
+        dfa.intern(LexemeAccepter::stream());
+        dfa.intern(vec![LexemeAccepter::Lit(LitAccepter::StrCont(
+            StrContAccepter::default(),
+        ))]);
+        dfa
+    }
+
+    /// Runs `f` with the DFA of the current thread.
+    pub(crate) fn with<T>(f: impl FnOnce(&mut Self) -> T) -> T {
+        DFA.with_borrow_mut(f)
+    }
+
+    /// The state reached from `state` by reading `c`.
+    pub(crate) fn next(&mut self, state: StateId, c: char) -> StateId {
+        if state != Self::UNCACHED {
+            let cached = match u8::try_from(c) {
+                Ok(b) if b.is_ascii() => self.states[state as usize].ascii[b as usize],
+                _ => *self.states[state as usize]
+                    .other
+                    .get(&c)
+                    .unwrap_or(&UNKNOWN),
+            };
+            if cached != UNKNOWN {
+                return cached;
+            }
+        }
+
+        let next = get_next_candidates(&self.state(state).candidates, c);
+        let next = self.intern(next);
+        if state == Self::UNCACHED || next == Self::UNCACHED {
+            return next;
+        }
+
+        let from = &mut self.states[state as usize];
+        match u8::try_from(c) {
+            Ok(b) if b.is_ascii() => from.ascii[b as usize] = next,
+            _ => {
+                from.other.insert(c, next);
+            }
+        }
+
+        next
+    }
+
+    /// The kinds accepting the text read to reach `state`.
+    pub(crate) fn accepted(&self, state: StateId) -> LexemeKindSet {
+        self.state(state).accepted
+    }
+
+    /// The candidates in `state`.
+    pub(crate) fn candidates(&self, state: StateId) -> &[LexemeAccepter] {
+        &self.state(state).candidates
+    }
+
+    fn state(&self, state: StateId) -> &State {
+        match state {
+            Self::UNCACHED => &self.uncached,
+            _ => &self.states[state as usize],
+        }
+    }
+
+    /// Gets the state made of `candidates`, creating it if it doesn't exist yet.
+    fn intern(&mut self, candidates: Vec<LexemeAccepter>) -> StateId {
+        if let Some(&id) = self.ids.get(candidates.as_slice()) {
+            return id;
+        }
+
+        let candidates = candidates.into_boxed_slice();
+        if self.states.len() >= self.max_states || !is_cacheable(&candidates) {
+            self.uncached = State::new(candidates);
+            return Self::UNCACHED;
+        }
+
+        let id = self.states.len() as StateId;
+        self.states.push(State::new(candidates.clone()));
+        self.ids.insert(candidates, id);
+
+        id
+    }
+}
+
+impl State {
+    fn new(candidates: Box<[LexemeAccepter]>) -> Self {
+        Self {
+            accepted: accepted_kinds(&candidates),
+            candidates,
+            ascii: [UNKNOWN; 128],
+            other: HashMap::new(),
+        }
+    }
+}
+
+/// Checks if the state made of `candidates` is worth caching, as it doesn't count too far.
+fn is_cacheable(candidates: &[LexemeAccepter]) -> bool {
+    candidates.iter().all(|candidate| match candidate {
+        LexemeAccepter::Comment(CommentAccepter::Block(block)) => block.depth() <= MAX_CACHED_DEPTH,
+        LexemeAccepter::Lit(LitAccepter::StrRaw(raw)) => raw.hashes() <= MAX_CACHED_HASHES,
+        _ => true,
+    })
+}
+
+/// Runs `accept` on every candidate in `candidates` and returns the ones that accept `c`.
+#[cfg(not(feature = "parallel"))]
+fn get_next_candidates(candidates: &[LexemeAccepter], c: char) -> Vec<LexemeAccepter> {
+    candidates.iter().filter_map(|s| s.accept(c)).collect()
+}
+
+/// Runs `accept` on every candidate in `candidates` in parallel and returns the ones that accept `c`.
+#[cfg(feature = "parallel")]
+fn get_next_candidates(candidates: &[LexemeAccepter], c: char) -> Vec<LexemeAccepter> {
+    use rayon::prelude::*;
+
+    candidates
+        .into_par_iter()
+        .filter_map(|s| s.accept(c))
+        .collect()
+}
+
+/// Returns the kinds of the acceptable candidates in `candidates`.
+#[cfg(not(feature = "parallel"))]
+fn accepted_kinds(candidates: &[LexemeAccepter]) -> LexemeKindSet {
+    candidates
+        .iter()
+        .filter(|s| s.acceptable())
+        .map(|&s| LexemeKind::from(s))
+        .collect()
+}
+
+/// Returns the kinds of the acceptable candidates in `candidates` in parallel.
+#[cfg(feature = "parallel")]
+fn accepted_kinds(candidates: &[LexemeAccepter]) -> LexemeKindSet {
+    use rayon::prelude::*;
+
+    candidates
+        .par_iter()
+        .filter(|s| s.acceptable())
+        .map(|&s| LexemeKind::from(s))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dfa_matches_candidates() {
+        let src = "val x := 0b10_1 + 1.5 - 'a' /* é */ \"→ ✅\" // 日本\n..= ... @m:x #t-1 $T";
+        let mut dfa = Dfa::new();
+
+        for start in src.char_indices().map(|(i, _)| i) {
+            let mut state = Dfa::START;
+            let mut candidates = LexemeAccepter::stream();
+
+            for c in src[start..].chars() {
+                state = dfa.next(state, c);
+                candidates = get_next_candidates(&candidates, c);
+
+                assert_eq!(dfa.candidates(state), candidates.as_slice());
+                assert_eq!(dfa.accepted(state), accepted_kinds(&candidates));
+                if state == Dfa::DEAD {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dfa_reuses_states() {
+        let mut dfa = Dfa::new();
+        let a = dfa.next(Dfa::START, 'x');
+        let states = dfa.states.len();
+
+        assert_eq!(dfa.next(Dfa::START, 'x'), a);
+        assert_eq!(dfa.next(Dfa::START, 'y'), a);
+        assert_eq!(dfa.next(a, '?'), Dfa::DEAD);
+        assert_eq!(dfa.states.len(), states);
+    }
+
+    #[test]
+    fn dfa_skips_deep_comments() {
+        let mut dfa = Dfa::new();
+        let src = "/*".repeat(1000) + &"*/".repeat(1000);
+        let lex = |dfa: &mut Dfa| src.chars().fold(Dfa::START, |state, c| dfa.next(state, c));
+
+        let state = lex(&mut dfa);
+        assert!(dfa.accepted(state).contains(LexemeKind::CommentBlock));
+        let states = dfa.states.len();
+        assert!(states < 4 * MAX_CACHED_DEPTH as usize + 16);
+
+        lex(&mut dfa);
+        assert_eq!(dfa.states.len(), states);
+    }
+
+    #[test]
+    fn dfa_skips_long_raw_strings() {
+        let mut dfa = Dfa::new();
+        let hashes = "#".repeat(1000);
+        let src = format!("r{hashes}\"a\"#\"{hashes}");
+
+        let state = src.chars().fold(Dfa::START, |state, c| dfa.next(state, c));
+        assert!(dfa.accepted(state).contains(LexemeKind::LitStrRaw));
+        assert!(dfa.states.len() < 4 * MAX_CACHED_HASHES as usize + 16);
+    }
+
+    #[test]
+    fn dfa_caps_cached_states() {
+        let src = "val x := 0b10_1 + 1.5 - 'a' /* é */ \"→ ✅\" // 日本";
+        let mut dfa = Dfa::new();
+        dfa.max_states = dfa.states.len();
+        let mut state = Dfa::START;
+        let mut candidates = LexemeAccepter::stream();
+
+        for c in src.chars() {
+            state = dfa.next(state, c);
+            candidates = get_next_candidates(&candidates, c);
+            assert_eq!(dfa.candidates(state), candidates.as_slice());
+            assert_eq!(dfa.accepted(state), accepted_kinds(&candidates));
+            if state == Dfa::DEAD {
+                state = Dfa::START;
+                candidates = LexemeAccepter::stream();
+            }
+        }
+
+        assert_eq!(dfa.states.len(), dfa.max_states);
+        // Known states are still reached from uncached ones
+        let x = dfa.next(Dfa::START, 'x');
+        assert_eq!(x, Dfa::UNCACHED);
+        assert_eq!(dfa.next(x, '?'), Dfa::DEAD);
+    }
+}
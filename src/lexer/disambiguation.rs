@@ -0,0 +1,257 @@
+use crate::{
+    lexeme::{kind::LexemeKindSet, Lexeme, LexemeAmbiguity},
+    LexemeKind,
+};
+
+/// The policy deciding when a keyword can be used as a value identifier.
+///
+/// A reserved keyword is always a keyword, while a contextual keyword is a keyword only where a
+/// keyword is expected and a value identifier anywhere else. By default every keyword is
+/// contextual.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeywordPolicy {
+    reserved: LexemeKindSet,
+}
+
+impl KeywordPolicy {
+    /// A policy where every keyword is contextual.
+    pub fn contextual() -> Self {
+        Self::default()
+    }
+
+    /// A policy where every keyword is reserved.
+    pub fn reserved() -> Self {
+        Self {
+            reserved: LexemeKind::ALL
+                .into_iter()
+                .filter(|kind| kind.is_keyword())
+                .collect(),
+        }
+    }
+
+    /// Makes the keyword `kw` reserved.
+    pub fn reserve(mut self, kw: LexemeKind) -> Self {
+        if kw.is_keyword() {
+            self.reserved.insert(kw);
+        }
+        self
+    }
+
+    /// Checks if the keyword `kw` is reserved.
+    pub fn is_reserved(&self, kw: LexemeKind) -> bool {
+        self.reserved.contains(kw)
+    }
+}
+
+/// Resolves every ambiguous lexeme in `lexemes` so the resulting lexemes are all unambiguous.
+///
+/// A lexeme that can either be a keyword or a value identifier is a keyword if the keyword is
+/// reserved by `policy`. Otherwise it is a value identifier right after a `.`, and a keyword when:
+/// - It starts a statement (it is the first lexeme in its line or comes after `;`, `{` or `}`) and
+///   is not followed by something a value would be followed by, such as `:=`, `=`, `.` or `,`.
+/// - It is `fn` followed by `(`, starting a lambda.
+/// - It is any keyword but `main` followed by an identifier, as in `(val x, type T)`.
+/// - It is `type` not followed by `:=`, `=` or `.`, so it stands for the type of types.
+///
+/// So in `val x := 1` and `add := fn (a Int) -> a` the `val` and `fn` are keywords, while in
+/// `x.type` and `type := 1` the `type` is a value identifier.
+///
+/// Whitespace and comments are not taken into account when looking at the neighbouring lexemes,
+/// except for line breaks ending a statement, so nothing past them follows a lexeme.
+pub fn disambiguate<'src>(
+    mut lexemes: Vec<Lexeme<'src>>,
+    policy: &KeywordPolicy,
+) -> Vec<Lexeme<'src>> {
+    for i in 0..lexemes.len() {
+        let LexemeAmbiguity::Ambiguous(kinds) = lexemes[i].kind else {
+            continue;
+        };
+
+        let kw = kinds.iter().find(|kind| kind.is_keyword());
+        let kind = match kw {
+            Some(kw) if kinds.contains(LexemeKind::IdentVal) => {
+                if policy.is_reserved(kw) || is_keyword_position(&lexemes, i, kw) {
+                    kw
+                } else {
+                    LexemeKind::IdentVal
+                }
+            }
+            _ => kinds.first().expect("ambiguous lexemes have kinds"),
+        };
+
+        lexemes[i].kind = LexemeAmbiguity::Unambiguous(kind);
+    }
+
+    lexemes
+}
+
+/// Checks if the lexeme at `i` is in a place where the keyword `kw` is expected.
+fn is_keyword_position(lexemes: &[Lexeme], i: usize, kw: LexemeKind) -> bool {
+    let prev = lexemes[..i]
+        .iter()
+        .rev()
+        .take_while(|lexeme| lexeme.kind.is_trivia() && !lexeme.slice.contains('\n'))
+        .count();
+    let starts_statement = match lexemes[..i - prev].last() {
+        None => true,
+        Some(lexeme) => {
+            lexeme.slice.contains('\n')
+                || matches!(
+                    lexeme.kind,
+                    LexemeAmbiguity::Unambiguous(
+                        LexemeKind::PtSemi | LexemeKind::DelimOBrace | LexemeKind::DelimCBrace
+                    )
+                )
+        }
+    };
+
+    let after_dot = lexemes[..i]
+        .iter()
+        .rev()
+        .find(|lexeme| !lexeme.kind.is_trivia())
+        .is_some_and(|lexeme| lexeme.kind == LexemeAmbiguity::Unambiguous(LexemeKind::PtDot));
+    if after_dot {
+        return false;
+    }
+
+    // A line break ends the statement, so nothing follows the lexeme past it
+    let next = lexemes[i + 1..]
+        .iter()
+        .find(|lexeme| !lexeme.kind.is_trivia() || lexeme.slice.contains('\n'))
+        .filter(|lexeme| !lexeme.kind.is_trivia());
+    let followed_by_value_use = match next {
+        None => true,
+        Some(lexeme) => matches!(
+            lexeme.kind,
+            LexemeAmbiguity::Unambiguous(
+                LexemeKind::OpDecl
+                    | LexemeKind::OpEq
+                    | LexemeKind::PtDot
+                    | LexemeKind::PtComma
+                    | LexemeKind::PtColon
+                    | LexemeKind::PtSemi
+                    | LexemeKind::DelimCParen
+                    | LexemeKind::DelimCBrack
+                    | LexemeKind::DelimCBrace
+                    | LexemeKind::Eof
+            )
+        ),
+    };
+
+    let next_kinds = next.map_or(LexemeKindSet::new(), |lexeme| lexeme.kind.kinds());
+    let followed_by_ident =
+        next_kinds.contains(LexemeKind::IdentVal) || next_kinds.contains(LexemeKind::IdentType);
+    let followed_by_binding = [LexemeKind::OpDecl, LexemeKind::OpEq, LexemeKind::PtDot]
+        .into_iter()
+        .any(|kind| next_kinds.contains(kind));
+    let in_expression = match kw {
+        LexemeKind::KwFn => next_kinds.contains(LexemeKind::DelimOParen),
+        LexemeKind::KwMain => false,
+        LexemeKind::KwType => followed_by_ident || next.is_some() && !followed_by_binding,
+        _ => followed_by_ident,
+    };
+
+    starts_statement && !followed_by_value_use || in_expression
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lex;
+
+    fn kinds<'src>(src: &'src str, policy: &KeywordPolicy) -> Vec<(LexemeKind, &'src str)> {
+        disambiguate(lex(src), policy)
+            .into_iter()
+            .filter(|lexeme| !lexeme.kind.is_trivia())
+            .map(|lexeme| (lexeme.kind.unambiguous_unchecked(), lexeme.slice))
+            .collect()
+    }
+
+    #[test]
+    fn contextual_keywords() {
+        let policy = KeywordPolicy::contextual();
+
+        assert_eq!(
+            kinds("val x := 10\ntype := x.type", &policy),
+            [
+                (LexemeKind::KwVal, "val"),
+                (LexemeKind::IdentVal, "x"),
+                (LexemeKind::OpDecl, ":="),
+                (LexemeKind::LitIntDec, "10"),
+                (LexemeKind::IdentVal, "type"),
+                (LexemeKind::OpDecl, ":="),
+                (LexemeKind::IdentVal, "x"),
+                (LexemeKind::PtDot, "."),
+                (LexemeKind::IdentVal, "type"),
+            ]
+        );
+        assert_eq!(
+            kinds("main -> { val := main }", &policy),
+            [
+                (LexemeKind::KwMain, "main"),
+                (LexemeKind::OpRArw, "->"),
+                (LexemeKind::DelimOBrace, "{"),
+                (LexemeKind::IdentVal, "val"),
+                (LexemeKind::OpDecl, ":="),
+                (LexemeKind::IdentVal, "main"),
+                (LexemeKind::DelimCBrace, "}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keywords_in_expressions() {
+        let policy = KeywordPolicy::contextual();
+        let kind_of = |src, word| {
+            kinds(src, &policy)
+                .into_iter()
+                .find(|&(_, slice)| slice == word)
+                .map(|(kind, _)| kind)
+        };
+
+        assert_eq!(
+            kind_of("add := fn (a Int) -> a", "fn"),
+            Some(LexemeKind::KwFn)
+        );
+        assert_eq!(kind_of("f(type)", "type"), Some(LexemeKind::KwType));
+        assert_eq!(kind_of("f(val x, type T)", "val"), Some(LexemeKind::KwVal));
+        assert_eq!(
+            kind_of("f(val x, type T)", "type"),
+            Some(LexemeKind::KwType)
+        );
+        assert_eq!(kind_of("x := y.fn(1)", "fn"), Some(LexemeKind::IdentVal));
+        assert_eq!(kind_of("f(fn, val)", "fn"), Some(LexemeKind::IdentVal));
+        assert_eq!(kind_of("f(fn, val)", "val"), Some(LexemeKind::IdentVal));
+        assert_eq!(kind_of("f(main)", "main"), Some(LexemeKind::IdentVal));
+        assert_eq!(
+            kind_of("x := val\ny := 2", "val"),
+            Some(LexemeKind::IdentVal)
+        );
+        assert_eq!(
+            kind_of("a := type\nb := 1", "type"),
+            Some(LexemeKind::IdentVal)
+        );
+    }
+
+    #[test]
+    fn reserved_keywords() {
+        let policy = KeywordPolicy::contextual().reserve(LexemeKind::KwType);
+
+        assert_eq!(
+            kinds("x.type + x.val", &policy),
+            [
+                (LexemeKind::IdentVal, "x"),
+                (LexemeKind::PtDot, "."),
+                (LexemeKind::KwType, "type"),
+                (LexemeKind::OpPlus, "+"),
+                (LexemeKind::IdentVal, "x"),
+                (LexemeKind::PtDot, "."),
+                (LexemeKind::IdentVal, "val"),
+            ]
+        );
+        assert!(disambiguate(lex("val fn type"), &KeywordPolicy::reserved())
+            .iter()
+            .filter(|lexeme| !lexeme.kind.is_trivia())
+            .all(|lexeme| lexeme.kind.unambiguous_unchecked().is_keyword()));
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt::Display;
+
+use crate::lexeme::{
+    number::{FltTy, IntTy},
+    Coord,
+};
+
+/// An error found while lexing the source code.
+///
+/// It carries the kind of the error along with the byte span and the coordinates of the
+/// offending text, so the parser can report it to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LexError {
+    pub kind: LexErrorKind,
+    pub start: usize,
+    pub end: usize,
+    pub start_coord: Coord,
+    pub end_coord: Coord,
+}
+
+/// The kinds of errors the lexer can report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LexErrorKind {
+    /// A character that can't start nor continue any lexeme.
+    UnexpectedChar(char),
+    /// A string literal missing its closing `"`.
+    UnterminatedStr,
+    /// An interpolation hole of a string missing its closing `}`, located at its opening `{`.
+    UnclosedHole,
+    /// A block comment missing its closing `*/`. Block comments nest, so it starts at the opening
+    /// `/*` of the outermost unclosed comment.
+    UnterminatedBlockComment,
+    /// A numeric literal that doesn't follow any of the numeric literal rules.
+    MalformedNumber,
+    /// A `0b`, `0o` or `0x` literal with a char that is not a digit of its radix, spanning the
+    /// whole literal. `at` is the byte offset of the first such char in the source code.
+    InvalidDigit { digit: char, radix: u32, at: usize },
+    /// An integer literal out of the range of its declared type, or of 128 bits if it has none.
+    IntOverflow(Option<IntTy>),
+    /// A floating point literal too large for its declared type, or for an `F64` if it has none.
+    FltOverflow(Option<FltTy>),
+    /// An escape sequence with an unknown escaped char, like `\q`.
+    UnknownEscape(char),
+    /// A `\x` or `\u` escape sequence that is not well formed or not a valid char.
+    MalformedEscape,
+    /// A lexeme whose value was decoded as a literal of another kind.
+    NotALiteral,
+}
+
+impl Display for LexErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedChar(c) => write!(f, "unexpected character `{}`", c.escape_debug()),
+            Self::UnterminatedStr => write!(f, "unterminated string literal"),
+            Self::UnclosedHole => write!(f, "unclosed interpolation hole"),
+            Self::UnterminatedBlockComment => write!(f, "unterminated block comment"),
+            Self::MalformedNumber => write!(f, "malformed numeric literal"),
+            Self::InvalidDigit { digit, radix, .. } => write!(
+                f,
+                "invalid digit `{}` in base {} literal",
+                digit.escape_debug(),
+                radix
+            ),
+            Self::IntOverflow(Some(ty)) => write!(f, "integer literal out of range for `{}`", ty),
+            Self::IntOverflow(None) => write!(f, "integer literal too large"),
+            Self::FltOverflow(Some(ty)) => write!(f, "float literal out of range for `{}`", ty),
+            Self::FltOverflow(None) => write!(f, "float literal too large"),
+            Self::UnknownEscape(c) => write!(f, "unknown escape sequence `\\{}`", c.escape_debug()),
+            Self::MalformedEscape => write!(f, "malformed escape sequence"),
+            Self::NotALiteral => write!(f, "not a literal of the expected kind"),
+        }
+    }
+}
+
+impl Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}", self.kind, self.start_coord)
+    }
+}
+
+impl std::error::Error for LexError {}
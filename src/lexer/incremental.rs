@@ -0,0 +1,238 @@
+use std::ops::Range;
+
+use super::{dfa::Dfa, track_holes, Hole, Lexer};
+use crate::lexeme::{kind::LexemeKind, Coord, Lexeme};
+
+/// An edit of the source code replacing the bytes in `range` by the `replacement` text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit<'a> {
+    pub range: Range<usize>,
+    pub replacement: &'a str,
+}
+
+impl TextEdit<'_> {
+    /// Applies the edit to `src`, returning the edited source code.
+    pub fn apply(&self, src: &str) -> String {
+        let mut edited =
+            String::with_capacity(src.len() - self.range.len() + self.replacement.len());
+        edited.push_str(&src[..self.range.start]);
+        edited.push_str(self.replacement);
+        edited.push_str(&src[self.range.end..]);
+
+        edited
+    }
+}
+
+/// Re-lexes the source code `src` after an `edit`, given the `lexemes` the source code had
+/// before the edit as returned by [`lex`](super::lex). The result is exactly what
+/// [`lex`](super::lex) returns for `src`.
+///
+/// Only the text around the edit is lexed again. Lexing restarts at the first lexeme the edit may
+/// have changed and stops as soon as the next lexeme would start where an old one did after the
+/// edit, in the same interpolation hole. The lexemes from there on are the old ones moved by the
+/// edit.
+///
+/// # Panics
+///
+/// Panics if the edit doesn't fit in the source code or its bounds are not on char boundaries.
+pub fn relex<'src>(lexemes: &[Lexeme], src: &'src str, edit: &TextEdit) -> Vec<Lexeme<'src>> {
+    let edit_start = edit.range.start;
+    let new_end = edit_start + edit.replacement.len();
+    let old_len = src.len() - edit.replacement.len() + edit.range.len();
+    debug_assert_eq!(&src[edit_start..new_end], edit.replacement);
+
+    // Lexemes barely read past their end, so once a lexeme doesn't read up to the edit the ones
+    // before it don't either. The text skipped after a lexeme is an error that may be gone now.
+    let mut first = lexemes.partition_point(|lexeme| lexeme.end < edit_start);
+    while let Some(previous) = first.checked_sub(1).map(|i| &lexemes[i]) {
+        let next_start = lexemes.get(first).map_or(old_len, |lexeme| lexeme.start);
+        if previous.end == next_start && !reads_up_to(src, previous, edit_start) {
+            break;
+        }
+        first -= 1;
+    }
+
+    let mut lexer = Lexer::new(src);
+    if let Some(previous) = first.checked_sub(1).map(|i| &lexemes[i]) {
+        lexer.pos = previous.end;
+        lexer.coord = previous.end_coord;
+    }
+    for lexeme in &lexemes[..first] {
+        track_holes(&mut lexer.holes, lexeme);
+    }
+
+    let mut relexed: Vec<_> = lexemes[..first]
+        .iter()
+        .map(|lexeme| Shift::NONE.apply(lexeme, src))
+        .collect();
+
+    // The next old lexeme a new one may line up with, and the holes it starts in
+    let mut old = first;
+    let mut old_holes = lexer.holes.clone();
+    loop {
+        if lexer.pos >= new_end {
+            let old_pos = lexer.pos - new_end + edit.range.end;
+            while let Some(lexeme) = lexemes.get(old).filter(|lexeme| lexeme.start < old_pos) {
+                track_holes(&mut old_holes, lexeme);
+                old += 1;
+            }
+
+            let aligned = lexemes
+                .get(old)
+                .filter(|lexeme| lexeme.start == old_pos && same_modes(&old_holes, &lexer.holes));
+            if let Some(anchor) = aligned {
+                let shift = Shift::between(anchor, &lexer);
+                relexed.extend(lexemes[old..].iter().map(|lexeme| shift.apply(lexeme, src)));
+                return relexed;
+            }
+        }
+
+        match lexer.next() {
+            Some(lexeme) => relexed.push(lexeme),
+            None => return relexed,
+        }
+    }
+}
+
+/// How far the lexemes after an edit moved.
+struct Shift {
+    bytes: isize,
+    lines: isize,
+    /// The old line of the first moved lexeme, the only one whose columns moved as well.
+    line: usize,
+    cols: isize,
+}
+
+impl Shift {
+    const NONE: Self = Self {
+        bytes: 0,
+        lines: 0,
+        line: 0,
+        cols: 0,
+    };
+
+    /// The shift moving the old lexeme `anchor` to where `lexer` is about to lex.
+    fn between(anchor: &Lexeme, lexer: &Lexer) -> Self {
+        Self {
+            bytes: lexer.pos as isize - anchor.start as isize,
+            lines: lexer.coord.line as isize - anchor.start_coord.line as isize,
+            line: anchor.start_coord.line,
+            cols: lexer.coord.col as isize - anchor.start_coord.col as isize,
+        }
+    }
+
+    /// Moves the old `lexeme` into the edited source code `src`.
+    fn apply<'src>(&self, lexeme: &Lexeme, src: &'src str) -> Lexeme<'src> {
+        let start = lexeme.start.wrapping_add_signed(self.bytes);
+        let end = lexeme.end.wrapping_add_signed(self.bytes);
+
+        Lexeme {
+            kind: lexeme.kind,
+            slice: &src[start..end],
+            start,
+            end,
+            start_coord: self.coord(lexeme.start_coord),
+            end_coord: self.coord(lexeme.end_coord),
+        }
+    }
+
+    fn coord(&self, coord: Coord) -> Coord {
+        Coord {
+            line: coord.line.wrapping_add_signed(self.lines),
+            col: if coord.line == self.line {
+                coord.col.wrapping_add_signed(self.cols)
+            } else {
+                coord.col
+            },
+        }
+    }
+}
+
+/// Checks if lexing `lexeme` again reads the char at `offset` of `src`, or the end of `src`.
+///
+/// The text before `offset` must be the same as when `lexeme` was lexed.
+fn reads_up_to(src: &str, lexeme: &Lexeme, offset: usize) -> bool {
+    let resumes_str = matches!(
+        lexeme.kind.unambiguous(),
+        Some(LexemeKind::LitStrMid | LexemeKind::LitStrTail)
+    );
+
+    Dfa::with(|dfa| {
+        let mut state = if resumes_str {
+            Dfa::STR_CONT
+        } else {
+            Dfa::START
+        };
+
+        for (i, c) in src[lexeme.start..].char_indices() {
+            if lexeme.start + i >= offset {
+                return true;
+            }
+
+            state = dfa.next(state, c);
+            if state == Dfa::DEAD {
+                return false;
+            }
+        }
+
+        true
+    })
+}
+
+/// Checks if two stacks of holes lex the same way, which only depends on the braces open in them.
+fn same_modes(a: &[Hole], b: &[Hole]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.depth == b.depth)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lex;
+
+    /// Applies `edit` to `src`, checking that re-lexing matches a full lex.
+    fn check(src: &str, range: Range<usize>, replacement: &str) {
+        let edit = TextEdit { range, replacement };
+        let edited = edit.apply(src);
+
+        assert_eq!(
+            relex(&lex(src), &edited, &edit),
+            lex(&edited),
+            "{src:?} -> {edited:?}"
+        );
+    }
+
+    #[test]
+    fn relex_edits() {
+        // Growing and merging lexemes
+        check("val x := 10\nval y := x", 10..10, "0");
+        check("a b", 1..2, "");
+        check("1 ..2", 1..2, "");
+        // Opening and closing strings and comments past many lines
+        check("x := 1\ny := \"a\"\nz := 2", 5..5, "\"");
+        check("x := \"abc\ny := 2", 9..9, "\"");
+        check("/* a */ b\nc */ d", 3..3, "/*");
+        // Interpolation holes
+        check("\"a {b} c\" d", 4..5, "{e}");
+        check("\"a {b} c\" d", 3..4, "");
+        check("f(\"{x}\")\ng", 4..4, "\"");
+        // Multi-byte chars and line shifts
+        check("é := 'λ'\n😀 x\ny", 0..2, "a\nb");
+        check("x\ny z", 2..2, "\n\n");
+    }
+
+    #[test]
+    fn relex_reuses_the_tail() {
+        let src = "val x := 1\nval y := 2";
+        let edit = TextEdit {
+            range: 9..10,
+            replacement: "100",
+        };
+        let edited = edit.apply(src);
+        let relexed = relex(&lex(src), &edited, &edit);
+
+        let last = relexed.last().unwrap();
+        assert_eq!((last.slice, last.start), ("2", 22));
+        assert_eq!(last.start_coord, Coord { line: 2, col: 10 });
+        assert_eq!(relexed, lex(&edited));
+    }
+}
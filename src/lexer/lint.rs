@@ -0,0 +1,351 @@
+use std::fmt::Display;
+
+use crate::lexeme::{kind::LexemeKind, Coord, Lexeme};
+
+/// A warning about source code that lexes fine but may not read as it runs.
+///
+/// Like a [`LexError`](super::error::LexError) it carries the byte span and the coordinates of the
+/// offending text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LintWarning {
+    pub kind: LintWarningKind,
+    pub start: usize,
+    pub end: usize,
+    pub start_coord: Coord,
+    pub end_coord: Coord,
+}
+
+/// The kinds of warnings reported by [`lint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintWarningKind {
+    /// An identifier with chars from more than one script, located at the first char of the
+    /// second script.
+    MixedScripts(Script, Script),
+    /// An identifier with a char that looks like an ASCII letter, located at that char, which has
+    /// Latin chars too or only such chars.
+    Confusable { found: char, looks_like: char },
+    /// A bidirectional override or isolate char in a literal or comment, which can make the code
+    /// display in a different order than it is read by the compiler (aka Trojan Source).
+    BidiControl(char),
+}
+
+/// The scripts told apart by [`lint`].
+///
+/// Chars shared by many scripts, such as digits, `_` and combining marks, have no script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Bengali,
+    Thai,
+    Georgian,
+    Hangul,
+    Hiragana,
+    Katakana,
+    Han,
+    Other,
+}
+
+impl Display for LintWarningKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MixedScripts(first, second) => {
+                write!(f, "identifier mixes {:?} and {:?} scripts", first, second)
+            }
+            Self::Confusable { found, looks_like } => write!(
+                f,
+                "identifier char `{}` (U+{:04X}) looks like `{}`",
+                found, *found as u32, looks_like
+            ),
+            Self::BidiControl(c) => {
+                write!(f, "bidirectional control char U+{:04X}", *c as u32)
+            }
+        }
+    }
+}
+
+impl Display for LintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}", self.kind, self.start_coord)
+    }
+}
+
+/// Looks for identifiers and literals that may be read differently than they are lexed.
+///
+/// Identifiers are flagged once if they mix scripts (Han, Hiragana, Katakana and Hangul mix
+/// freely, as they are written together) and once if they have a char confusable with an ASCII
+/// letter, like the Cyrillic `а`. Confusable chars are only flagged along with Latin chars or when
+/// every letter is confusable (like the Cyrillic `рау`), so names written in a single script don't
+/// get flagged for their ordinary letters. Every bidirectional override or isolate char in a string
+/// or char literal or in a comment is flagged too.
+pub fn lint(lexemes: &[Lexeme]) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for lexeme in lexemes {
+        let kinds = lexeme.kind.kinds();
+        if kinds.iter().any(is_ident) {
+            lint_ident(lexeme, &mut warnings);
+        } else if kinds.iter().any(may_hide_bidi) {
+            for (i, c) in lexeme.slice.char_indices() {
+                if is_bidi_control(c) {
+                    warnings.push(warning(lexeme, LintWarningKind::BidiControl(c), i, c));
+                }
+            }
+        }
+    }
+
+    warnings
+}
+
+fn lint_ident(lexeme: &Lexeme, warnings: &mut Vec<LintWarning>) {
+    if lexeme.slice.is_ascii() {
+        return;
+    }
+
+    let letters = || lexeme.slice.chars().filter(|&c| script(c).is_some());
+    let spoofs_latin = letters().any(|c| script(c) == Some(Script::Latin))
+        || letters().all(|c| ascii_confusable(c).is_some());
+
+    let mut first_script = None;
+    let mut mixed = false;
+    let mut confusable = false;
+    for (i, c) in lexeme.slice.char_indices() {
+        if let Some(script) = script(c) {
+            match first_script {
+                None => first_script = Some(script),
+                Some(first) if !mixed && !same_writing(first, script) => {
+                    mixed = true;
+                    let kind = LintWarningKind::MixedScripts(first, script);
+                    warnings.push(warning(lexeme, kind, i, c));
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(looks_like) = ascii_confusable(c).filter(|_| spoofs_latin && !confusable) {
+            confusable = true;
+            let kind = LintWarningKind::Confusable {
+                found: c,
+                looks_like,
+            };
+            warnings.push(warning(lexeme, kind, i, c));
+        }
+    }
+}
+
+/// Builds a warning located at the char `c` at `offset` inside `lexeme`.
+fn warning(lexeme: &Lexeme, kind: LintWarningKind, offset: usize, c: char) -> LintWarning {
+    let end = offset + c.len_utf8();
+
+    LintWarning {
+        kind,
+        start: lexeme.start + offset,
+        end: lexeme.start + end,
+        start_coord: lexeme.coord_at(offset),
+        end_coord: lexeme.coord_at(end),
+    }
+}
+
+fn is_ident(kind: LexemeKind) -> bool {
+    matches!(
+        kind,
+        LexemeKind::IdentVal
+            | LexemeKind::IdentType
+            | LexemeKind::IdentTag
+            | LexemeKind::IdentMacro
+            | LexemeKind::IdentSubtype
+    )
+}
+
+fn may_hide_bidi(kind: LexemeKind) -> bool {
+    kind.is_str_literal() || kind == LexemeKind::LitChr || kind.is_comment()
+}
+
+/// Checks if `c` is one of the explicit embedding, override or isolate chars.
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Checks if two scripts are commonly written together in the same word.
+fn same_writing(a: Script, b: Script) -> bool {
+    let cjk = |script| {
+        matches!(
+            script,
+            Script::Han | Script::Hiragana | Script::Katakana | Script::Hangul
+        )
+    };
+    a == b || cjk(a) && cjk(b)
+}
+
+/// The script of `c`, or `None` if it is shared by many scripts.
+fn script(c: char) -> Option<Script> {
+    let script = match c {
+        'A'..='Z'
+        | 'a'..='z'
+        | '\u{AA}'
+        | '\u{BA}'
+        | '\u{C0}'..='\u{D6}'
+        | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{2AF}'
+        | '\u{1E00}'..='\u{1EFF}'
+        | '\u{2C60}'..='\u{2C7F}'
+        | '\u{A720}'..='\u{A7FF}'
+        | '\u{FF21}'..='\u{FF3A}'
+        | '\u{FF41}'..='\u{FF5A}' => Script::Latin,
+        '\u{370}'..='\u{3FF}' | '\u{1F00}'..='\u{1FFF}' => Script::Greek,
+        '\u{400}'..='\u{52F}'
+        | '\u{1C80}'..='\u{1C8F}'
+        | '\u{2DE0}'..='\u{2DFF}'
+        | '\u{A640}'..='\u{A69F}' => Script::Cyrillic,
+        '\u{530}'..='\u{58F}' => Script::Armenian,
+        '\u{590}'..='\u{5FF}' => Script::Hebrew,
+        '\u{600}'..='\u{6FF}' | '\u{750}'..='\u{77F}' => Script::Arabic,
+        '\u{900}'..='\u{97F}' => Script::Devanagari,
+        '\u{980}'..='\u{9FF}' => Script::Bengali,
+        '\u{E00}'..='\u{E7F}' => Script::Thai,
+        '\u{10A0}'..='\u{10FF}' => Script::Georgian,
+        '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' | '\u{AC00}'..='\u{D7AF}' => {
+            Script::Hangul
+        }
+        '\u{3040}'..='\u{309F}' => Script::Hiragana,
+        '\u{30A0}'..='\u{30FF}' => Script::Katakana,
+        '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{20000}'..='\u{2A6DF}' => {
+            Script::Han
+        }
+        c if c.is_alphabetic() => Script::Other,
+        _ => return None,
+    };
+
+    Some(script)
+}
+
+/// The ASCII letter that the non-ASCII `c` can be mistaken for, if any.
+fn ascii_confusable(c: char) -> Option<char> {
+    let ascii = match c {
+        // Fullwidth forms
+        '\u{FF21}'..='\u{FF3A}' | '\u{FF41}'..='\u{FF5A}' => {
+            char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)
+        }
+        // Cyrillic
+        'а' => 'a',
+        'е' => 'e',
+        'о' => 'o',
+        'р' => 'p',
+        'с' => 'c',
+        'у' => 'y',
+        'х' => 'x',
+        'ѕ' => 's',
+        'і' => 'i',
+        'ј' => 'j',
+        'ԁ' => 'd',
+        'ԛ' => 'q',
+        'ԝ' => 'w',
+        'һ' => 'h',
+        'ӏ' => 'l',
+        'А' => 'A',
+        'В' => 'B',
+        'Е' => 'E',
+        'К' => 'K',
+        'М' => 'M',
+        'Н' => 'H',
+        'О' => 'O',
+        'Р' => 'P',
+        'С' => 'C',
+        'Т' => 'T',
+        'Х' => 'X',
+        'Ѕ' => 'S',
+        'І' => 'I',
+        'Ј' => 'J',
+        'Ү' => 'Y',
+        'Ԛ' => 'Q',
+        'Ԝ' => 'W',
+        // Greek
+        'ο' => 'o',
+        'ν' => 'v',
+        'ρ' => 'p',
+        'ι' => 'i',
+        'Α' => 'A',
+        'Β' => 'B',
+        'Ε' => 'E',
+        'Ζ' => 'Z',
+        'Η' => 'H',
+        'Ι' => 'I',
+        'Κ' => 'K',
+        'Μ' => 'M',
+        'Ν' => 'N',
+        'Ο' => 'O',
+        'Ρ' => 'P',
+        'Τ' => 'T',
+        'Υ' => 'Y',
+        'Χ' => 'X',
+        // Latin
+        'ı' => 'i',
+        _ => return None,
+    };
+
+    Some(ascii)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lex;
+
+    #[test]
+    fn bidi_controls() {
+        let src = "s := \"admin\u{202E} \u{2066}// x\" // \u{2067}ok\nx := 1";
+        let warnings = lint(&lex(src));
+
+        let kinds: Vec<_> = warnings.iter().map(|warning| warning.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                LintWarningKind::BidiControl('\u{202E}'),
+                LintWarningKind::BidiControl('\u{2066}'),
+                LintWarningKind::BidiControl('\u{2067}'),
+            ]
+        );
+        assert_eq!(&src[warnings[0].start..warnings[0].end], "\u{202E}");
+        assert_eq!(warnings[0].start_coord, Coord { line: 1, col: 12 });
+        assert_eq!(warnings[2].start_coord, Coord { line: 1, col: 24 });
+    }
+
+    #[test]
+    fn clean_sources() {
+        assert!(lint(&lex("val x := \"crème brûlée\" // naïve")).is_empty());
+    }
+
+    #[cfg(feature = "unicode-idents")]
+    #[test]
+    fn confusable_idents() {
+        // The `а` is Cyrillic
+        let src = "pаypal := 1\nпривет := 2\nλx := 3\n日本ごかな := 4\nслово όνομα рау";
+        let warnings = lint(&lex(src));
+
+        let kinds: Vec<_> = warnings.iter().map(|warning| warning.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                LintWarningKind::MixedScripts(Script::Latin, Script::Cyrillic),
+                LintWarningKind::Confusable {
+                    found: 'а',
+                    looks_like: 'a'
+                },
+                LintWarningKind::MixedScripts(Script::Greek, Script::Latin),
+                LintWarningKind::Confusable {
+                    found: 'р',
+                    looks_like: 'p'
+                },
+            ]
+        );
+        assert_eq!(warnings[0].start_coord, Coord { line: 1, col: 2 });
+        assert_eq!((warnings[0].start, warnings[0].end), (1, 3));
+        assert_eq!(warnings[2].start_coord, Coord { line: 3, col: 2 });
+        assert_eq!(warnings[3].start_coord, Coord { line: 5, col: 13 });
+    }
+}
@@ -0,0 +1,337 @@
+use std::fmt::Display;
+
+use crate::lexeme::{kind::LexemeKind, Coord, Lexeme};
+
+/// A tree of lexemes, either a single lexeme or a group of trees delimited by matching brackets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenTree<'src> {
+    Leaf(Lexeme<'src>),
+    Group(Group<'src>),
+}
+
+/// Trees delimited by an opening bracket and the matching closing one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group<'src> {
+    pub delimiter: Delimiter,
+    pub open: Lexeme<'src>,
+    /// The lexeme closing the group, missing if it was never closed.
+    ///
+    /// It is a different bracket than `open` if they are mismatched.
+    pub close: Option<Lexeme<'src>>,
+    pub trees: Vec<TokenTree<'src>>,
+}
+
+/// The brackets delimiting a [`Group`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Delimiter {
+    /// `(` and `)`
+    Paren,
+    /// `[` and `]`
+    Brack,
+    /// `{` and `}`
+    Brace,
+}
+
+/// An error found while matching brackets, located by the lexemes of the brackets involved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DelimError<'src> {
+    /// A group closed by a different bracket than the one opening it.
+    Mismatched {
+        open: Lexeme<'src>,
+        close: Lexeme<'src>,
+    },
+    /// A group never closed, along with where its closing bracket likely belongs.
+    Unclosed {
+        open: Lexeme<'src>,
+        expected: usize,
+        expected_coord: Coord,
+    },
+    /// A closing bracket without any group to close.
+    Unopened { close: Lexeme<'src> },
+}
+
+impl<'src> TokenTree<'src> {
+    /// The first lexeme of the tree.
+    pub fn first(&self) -> &Lexeme<'src> {
+        match self {
+            Self::Leaf(lexeme) => lexeme,
+            Self::Group(group) => &group.open,
+        }
+    }
+
+    /// The last lexeme of the tree.
+    pub fn last(&self) -> &Lexeme<'src> {
+        match self {
+            Self::Leaf(lexeme) => lexeme,
+            Self::Group(group) => match (&group.close, group.trees.last()) {
+                (Some(close), _) => close,
+                (None, Some(tree)) => tree.last(),
+                (None, None) => &group.open,
+            },
+        }
+    }
+}
+
+impl Delimiter {
+    /// The delimiter of a bracket `kind`, and whether it is the opening bracket.
+    fn of(kind: LexemeKind) -> Option<(Self, bool)> {
+        let delimiter = match kind {
+            LexemeKind::DelimOParen => (Self::Paren, true),
+            LexemeKind::DelimCParen => (Self::Paren, false),
+            LexemeKind::DelimOBrack => (Self::Brack, true),
+            LexemeKind::DelimCBrack => (Self::Brack, false),
+            LexemeKind::DelimOBrace => (Self::Brace, true),
+            LexemeKind::DelimCBrace => (Self::Brace, false),
+            _ => return None,
+        };
+
+        Some(delimiter)
+    }
+}
+
+impl Display for DelimError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mismatched { open, close } => write!(
+                f,
+                "mismatched closing `{}` at {} for `{}` at {}",
+                close.slice, close.start_coord, open.slice, open.start_coord
+            ),
+            Self::Unclosed {
+                open,
+                expected_coord,
+                ..
+            } => write!(
+                f,
+                "unclosed `{}` at {}, likely missing its closing bracket at {}",
+                open.slice, open.start_coord, expected_coord
+            ),
+            Self::Unopened { close } => {
+                write!(
+                    f,
+                    "unexpected closing `{}` at {}",
+                    close.slice, close.start_coord
+                )
+            }
+        }
+    }
+}
+
+/// A group being built, whose closing bracket is still ahead.
+struct OpenGroup<'src> {
+    delimiter: Delimiter,
+    open: Lexeme<'src>,
+    trees: Vec<TokenTree<'src>>,
+    /// The indentation of the line the group is opened in.
+    indent: usize,
+}
+
+/// Groups `lexemes` into trees by matching their brackets, reporting the brackets that don't
+/// match. Whitespace and comments are left out of the trees.
+///
+/// A closing bracket closes the innermost group it matches, leaving the groups opened after that
+/// one unclosed. A closing bracket starting a line skips the matching groups opened in lines
+/// indented more than its own, as it most likely closes an outer one. If it matches no group it
+/// closes the innermost one anyway, as mismatched, or stands on its own outside of any group.
+///
+/// The closing bracket of an unclosed group is expected right before the first line indented no
+/// more than the line opening the group, so the trees from that line on are moved out of it.
+pub fn token_trees<'src>(
+    lexemes: impl IntoIterator<Item = Lexeme<'src>>,
+) -> (Vec<TokenTree<'src>>, Vec<DelimError<'src>>) {
+    let mut trees = Vec::new();
+    let mut groups: Vec<OpenGroup<'src>> = Vec::new();
+    let mut errors = Vec::new();
+    // The line of the last lexeme and the indentation of its line
+    let mut line = 0;
+    let mut indent = 0;
+
+    for lexeme in lexemes {
+        if lexeme.kind.is_trivia() {
+            continue;
+        }
+
+        let first_in_line = lexeme.start_coord.line > line;
+        if first_in_line {
+            indent = lexeme.start_coord.col - 1;
+        }
+        line = lexeme.end_coord.line;
+
+        match lexeme.kind.unambiguous().and_then(Delimiter::of) {
+            Some((delimiter, true)) => groups.push(OpenGroup {
+                delimiter,
+                open: lexeme,
+                trees: vec![],
+                indent,
+            }),
+            Some((delimiter, false)) => {
+                let matches = |group: &OpenGroup| group.delimiter == delimiter;
+                let aligned = |group: &OpenGroup| matches(group) && group.indent <= indent;
+                let matching = groups
+                    .iter()
+                    .rposition(|group| first_in_line && aligned(group))
+                    .or_else(|| groups.iter().rposition(matches));
+
+                match matching {
+                    Some(matching) => {
+                        while groups.len() > matching + 1 {
+                            close_unclosed(&mut groups, &mut trees, &mut errors);
+                        }
+                    }
+                    None => match groups.last() {
+                        Some(group) => errors.push(DelimError::Mismatched {
+                            open: group.open,
+                            close: lexeme,
+                        }),
+                        None => {
+                            errors.push(DelimError::Unopened { close: lexeme });
+                            trees.push(TokenTree::Leaf(lexeme));
+                            continue;
+                        }
+                    },
+                }
+
+                let group = groups.pop().expect("a group to close");
+                let group = TokenTree::Group(Group {
+                    delimiter: group.delimiter,
+                    open: group.open,
+                    close: Some(lexeme),
+                    trees: group.trees,
+                });
+                innermost(&mut groups, &mut trees).push(group);
+            }
+            None => innermost(&mut groups, &mut trees).push(TokenTree::Leaf(lexeme)),
+        }
+    }
+
+    while !groups.is_empty() {
+        close_unclosed(&mut groups, &mut trees, &mut errors);
+    }
+
+    (trees, errors)
+}
+
+/// The trees of the innermost group being built, or the top level trees if there is none.
+fn innermost<'a, 'src>(
+    groups: &'a mut [OpenGroup<'src>],
+    trees: &'a mut Vec<TokenTree<'src>>,
+) -> &'a mut Vec<TokenTree<'src>> {
+    groups.last_mut().map_or(trees, |group| &mut group.trees)
+}
+
+/// Gives up on the innermost group being built, reporting it as unclosed and moving the trees
+/// after where its closing bracket likely belongs out of it.
+fn close_unclosed<'src>(
+    groups: &mut Vec<OpenGroup<'src>>,
+    trees: &mut Vec<TokenTree<'src>>,
+    errors: &mut Vec<DelimError<'src>>,
+) {
+    let mut group = groups.pop().expect("a group to close");
+
+    let dedent = (0..group.trees.len()).find(|&i| {
+        let previous_line = match i {
+            0 => group.open.end_coord.line,
+            _ => group.trees[i - 1].last().end_coord.line,
+        };
+        let first = group.trees[i].first();
+
+        first.start_coord.line > previous_line && first.start_coord.col - 1 <= group.indent
+    });
+    let moved = group.trees.split_off(dedent.unwrap_or(group.trees.len()));
+
+    let last = group.trees.last().map_or(&group.open, TokenTree::last);
+    errors.push(DelimError::Unclosed {
+        open: group.open,
+        expected: last.end,
+        expected_coord: last.end_coord,
+    });
+
+    let parent = innermost(groups, trees);
+    parent.push(TokenTree::Group(Group {
+        delimiter: group.delimiter,
+        open: group.open,
+        close: None,
+        trees: group.trees,
+    }));
+    parent.extend(moved);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lex;
+
+    /// Writes the trees back as text, with `?` standing for a missing closing bracket.
+    fn text(trees: &[TokenTree]) -> String {
+        trees
+            .iter()
+            .map(|tree| match tree {
+                TokenTree::Leaf(lexeme) => lexeme.slice.to_string(),
+                TokenTree::Group(group) => format!(
+                    "{}{}{}",
+                    group.open.slice,
+                    text(&group.trees),
+                    group.close.map_or("?", |close| close.slice)
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn match_brackets() {
+        let (trees, errors) = token_trees(lex("f(a, [1, 2]) {\n    g(x)\n}"));
+
+        assert!(errors.is_empty());
+        assert_eq!(text(&trees), "f (a , [1 , 2]) {g (x)}");
+        let TokenTree::Group(group) = &trees[1] else {
+            panic!("expected a group");
+        };
+        assert_eq!(group.delimiter, Delimiter::Paren);
+        assert_eq!(group.trees.len(), 3);
+    }
+
+    #[test]
+    fn mismatched_brackets() {
+        let (trees, errors) = token_trees(lex("(a]\n)"));
+
+        assert_eq!(text(&trees), "(a] )");
+        assert!(matches!(
+            errors[..],
+            [
+                DelimError::Mismatched { open, close },
+                DelimError::Unopened { close: unopened },
+            ] if open.start == 0 && close.start == 2 && unopened.start == 4
+        ));
+    }
+
+    #[test]
+    fn unclosed_brackets() {
+        let src = "fn main() {\n    if x {\n        a(b\n    c\n}\nd";
+        let (trees, errors) = token_trees(lex(src));
+
+        assert_eq!(text(&trees), "fn main () {if x {a (b?? c} d");
+        let expected: Vec<_> = errors
+            .iter()
+            .map(|error| match error {
+                DelimError::Unclosed {
+                    open,
+                    expected_coord,
+                    ..
+                } => (open.start_coord, *expected_coord),
+                _ => panic!("unexpected {error:?}"),
+            })
+            .collect();
+        assert_eq!(
+            expected,
+            [
+                (Coord { line: 3, col: 10 }, Coord { line: 3, col: 12 }),
+                (Coord { line: 2, col: 10 }, Coord { line: 3, col: 12 }),
+            ]
+        );
+
+        let (trees, errors) = token_trees(lex("[a, (b"));
+        assert_eq!(text(&trees), "[a , (b??");
+        assert_eq!(errors.len(), 2);
+    }
+}
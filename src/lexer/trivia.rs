@@ -0,0 +1,167 @@
+use std::fmt::Display;
+
+use super::Lexer;
+use crate::lexeme::{kind::LexemeKind, Coord, Lexeme, LexemeAmbiguity};
+
+/// A significant lexeme along with the trivia around it, that is the whitespace, comments and
+/// text that can't be lexed a parser can skip but a formatter has to keep.
+///
+/// Every trivia lexeme belongs to exactly one token, following these rules:
+/// - The trivia following a token on the same line trails it. It ends right before the first
+///   trivia lexeme with a line break, be it whitespace or a block comment.
+/// - Any other trivia leads the next token, so a comment on its own line goes with the code below
+///   it.
+///
+/// So the text of the tokens in order is the source code they were lexed from, byte for byte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'src> {
+    pub leading: Vec<Lexeme<'src>>,
+    pub lexeme: Lexeme<'src>,
+    pub trailing: Vec<Lexeme<'src>>,
+}
+
+impl Token<'_> {
+    /// The byte offset where the token starts, leading trivia included.
+    pub fn full_start(&self) -> usize {
+        self.leading.first().unwrap_or(&self.lexeme).start
+    }
+
+    /// The byte offset where the token ends, trailing trivia included.
+    pub fn full_end(&self) -> usize {
+        self.trailing.last().unwrap_or(&self.lexeme).end
+    }
+}
+
+impl Display for Token<'_> {
+    /// Writes the text of the token along with its trivia.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for lexeme in self
+            .leading
+            .iter()
+            .chain([&self.lexeme])
+            .chain(&self.trailing)
+        {
+            f.write_str(lexeme.slice)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Lexes `src` into tokens carrying their trivia, ending with a [`LexemeKind::Eof`] token that
+/// leads with the trivia at the end of the source code.
+///
+/// Text that can't be lexed is kept as [`LexemeKind::Unknown`] trivia, so no text is missing from
+/// the tokens.
+pub fn lex_with_trivia(src: &str) -> Vec<Token<'_>> {
+    // The end of the last lexeme, where any skipped text starts
+    let mut end = (0, Coord { line: 1, col: 1 });
+    let lexemes = Lexer::new(src).with_eof().flat_map(|lexeme| {
+        let skipped = (lexeme.start > end.0).then(|| Lexeme {
+            kind: LexemeAmbiguity::Unambiguous(LexemeKind::Unknown),
+            slice: &src[end.0..lexeme.start],
+            start: end.0,
+            end: lexeme.start,
+            start_coord: end.1,
+            end_coord: lexeme.start_coord,
+        });
+        end = (lexeme.end, lexeme.end_coord);
+
+        skipped.into_iter().chain([lexeme])
+    });
+
+    attach_trivia(lexemes)
+}
+
+/// Attaches the trivia among `lexemes` to the significant lexemes around it (see [`Token`]).
+///
+/// Trivia after the last significant lexeme trails it, as there is no token to lead. End the
+/// lexemes with a [`LexemeKind::Eof`] to keep that trivia apart, or if there may be no
+/// significant lexeme at all, since trivia without a token is dropped.
+pub fn attach_trivia<'src>(lexemes: impl IntoIterator<Item = Lexeme<'src>>) -> Vec<Token<'src>> {
+    let mut tokens: Vec<Token<'src>> = Vec::new();
+    let mut leading = Vec::new();
+    // Whether trivia still trails the last token, as no line break was found after it
+    let mut trailing = false;
+
+    for lexeme in lexemes {
+        if !lexeme.kind.is_trivia() {
+            tokens.push(Token {
+                leading: std::mem::take(&mut leading),
+                lexeme,
+                trailing: vec![],
+            });
+            trailing = true;
+            continue;
+        }
+
+        trailing &= !lexeme.slice.contains('\n');
+        match tokens.last_mut() {
+            Some(token) if trailing => token.trailing.push(lexeme),
+            _ => leading.push(lexeme),
+        }
+    }
+
+    if let Some(token) = tokens.last_mut() {
+        token.trailing.append(&mut leading);
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lex;
+
+    /// The slices of some lexemes, to compare them at a glance.
+    fn slices<'src>(lexemes: &[Lexeme<'src>]) -> Vec<&'src str> {
+        lexemes.iter().map(|lexeme| lexeme.slice).collect()
+    }
+
+    #[test]
+    fn attach_trivia_to_tokens() {
+        let src = "// header\nval x := 1 // one\n\n/* two\n */ y /* a */ ;  \n";
+        let tokens = lex_with_trivia(src);
+
+        let token_slices: Vec<_> = tokens.iter().map(|token| token.lexeme.slice).collect();
+        assert_eq!(token_slices, ["val", "x", ":=", "1", "y", ";", ""]);
+
+        assert_eq!(slices(&tokens[0].leading), ["// header", "\n"]);
+        assert_eq!(slices(&tokens[0].trailing), [" "]);
+        assert_eq!(slices(&tokens[3].trailing), [" ", "// one"]);
+        assert_eq!(slices(&tokens[4].leading), ["\n\n", "/* two\n */", " "]);
+        assert_eq!(slices(&tokens[4].trailing), [" ", "/* a */", " "]);
+        assert!(tokens[5].trailing.is_empty());
+        assert_eq!(slices(&tokens[6].leading), ["  \n"]);
+        assert_eq!(tokens[6].lexeme.kind.unambiguous(), Some(LexemeKind::Eof));
+
+        assert_eq!((tokens[4].full_start(), tokens[4].full_end()), (27, 50));
+        assert_eq!(tokens.iter().map(Token::to_string).collect::<String>(), src);
+    }
+
+    #[test]
+    fn keep_skipped_text() {
+        let src = "x ? y\n#";
+        let tokens = lex_with_trivia(src);
+
+        assert_eq!(slices(&tokens[0].trailing), [" ", "?", " "]);
+        assert_eq!(
+            tokens[0].trailing[1].kind.unambiguous(),
+            Some(LexemeKind::Unknown)
+        );
+        assert_eq!(tokens[0].trailing[1].start_coord, Coord { line: 1, col: 3 });
+        assert_eq!(slices(&tokens[2].leading), ["\n", "#"]);
+        assert_eq!(tokens.iter().map(Token::to_string).collect::<String>(), src);
+    }
+
+    #[test]
+    fn attach_trivia_without_eof() {
+        let tokens = attach_trivia(lex("  x \n "));
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(slices(&tokens[0].leading), ["  "]);
+        assert_eq!(slices(&tokens[0].trailing), [" \n "]);
+        assert!(attach_trivia(lex(" // nothing")).is_empty());
+    }
+}
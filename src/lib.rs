@@ -1,7 +1,14 @@
 pub mod lexeme;
 pub mod lexer;
 
-pub use lexeme::kind::LexemeKind;
+pub use lexeme::kind::{LexemeKind, LexemeKindSet};
 pub use lexeme::Coord;
 pub use lexeme::Lexeme;
-pub use lexer::lex;
+pub use lexer::buffer::{lex_to_buffer, TokenBuffer};
+pub use lexer::disambiguation::{disambiguate, KeywordPolicy};
+pub use lexer::error::{LexError, LexErrorKind};
+pub use lexer::incremental::{relex, TextEdit};
+pub use lexer::lint::{lint, LintWarning, LintWarningKind};
+pub use lexer::tree::{token_trees, DelimError, Delimiter, Group, TokenTree};
+pub use lexer::trivia::{attach_trivia, lex_with_trivia, Token};
+pub use lexer::{lex, lex_with_eof, lex_with_errors, Lexer};
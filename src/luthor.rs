@@ -1,6 +1,6 @@
 use std::env::args;
 
-use aura_lex::lexer::lex;
+use aura_lex::lexer::{lex_with_errors, lint::lint, tree::token_trees};
 
 fn main() {
     if args().len() != 2 {
@@ -9,6 +9,15 @@ fn main() {
     }
     let filename = args().nth(1).expect("no filename provided");
     let src = std::fs::read_to_string(filename).expect("failed to read file");
-    let lexemes = lex(&src);
+    let (lexemes, errors) = lex_with_errors(&src);
     println!("{:#?}", lexemes);
+    for error in errors {
+        eprintln!("error: {}", error);
+    }
+    for error in token_trees(lexemes.iter().copied()).1 {
+        eprintln!("error: {}", error);
+    }
+    for warning in lint(&lexemes) {
+        eprintln!("warning: {}", warning);
+    }
 }
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use aura_lex::lexer::{
+        incremental::{relex, TextEdit},
+        lex,
+    };
+
+    /// Bits of source code that combine into every kind of lexeme, along with broken ones.
+    const FRAGMENTS: [&str; 34] = [
+        "val", "x", "Type", "main", " ", "\n", "\t", "1", "0x", "F", "_", "U8", "e", ".", "..",
+        "=", ":", "\"", "\"\"\"", "{", "}", "(", ")", "\\", "/", "*", "'", "r", "#", "é", "😀",
+        "+", "-", "?",
+    ];
+
+    /// A xorshift generator, good enough to pick fragments and edits reproducibly.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n as u64) as usize
+        }
+
+        fn source(&mut self, max_fragments: usize) -> String {
+            (0..self.below(max_fragments + 1))
+                .map(|_| FRAGMENTS[self.below(FRAGMENTS.len())])
+                .collect()
+        }
+
+        fn boundary(&mut self, src: &str) -> usize {
+            let boundaries: Vec<_> = src
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain([src.len()])
+                .collect();
+            boundaries[self.below(boundaries.len())]
+        }
+    }
+
+    #[test]
+    fn relex_matches_lex() {
+        let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+
+        for _ in 0..20_000 {
+            let src = rng.source(40);
+            let (a, b) = (rng.boundary(&src), rng.boundary(&src));
+            let replacement = rng.source(4);
+            let edit = TextEdit {
+                range: a.min(b)..a.max(b),
+                replacement: &replacement,
+            };
+            let edited = edit.apply(&src);
+
+            assert_eq!(
+                relex(&lex(&src), &edited, &edit),
+                lex(&edited),
+                "{:?} with {:?} replaced by {:?}",
+                src,
+                edit.range,
+                replacement
+            );
+        }
+    }
+}
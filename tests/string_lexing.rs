@@ -4,7 +4,7 @@ mod tests {
 
     #[test]
     fn lex_string() {
-        let src = r#""Hello World" "#;
+        let src = r#""Hello World""#;
         let lexemes = lex(src);
 
         assert!(lexemes.len() == 1);
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use aura_lex::{
+        lexeme::{kind::LexemeKind, Coord},
+        lexer::{lex, lex_with_errors, trivia::lex_with_trivia},
+    };
+
+    /// Sources mixing many scripts and symbols in strings and comments.
+    const CORPUS: [&str; 8] = [
+        "val café := \"crème brûlée\"",
+        "// résumé → naïve\nval x := 10",
+        "name := \"こんにちは世界\" /* 日本語 */",
+        "greet := \"Привет, мир!\" // по-русски",
+        "emoji := \"🦀🚀 done ✅\"\nnext := 'ok",
+        "/* ελληνικά */ \"عربي\" // हिन्दी",
+        "arrow := '→' star := '★'\nsmile := '😀'",
+        "nombre := 0xZZTOP + 0b102 // número mal formé",
+    ];
+
+    #[test]
+    fn slices_are_byte_ranges() {
+        for src in CORPUS {
+            let (lexemes, _) = lex_with_errors(src);
+            for lexeme in lexemes {
+                assert_eq!(&src[lexeme.start..lexeme.end], lexeme.slice);
+            }
+        }
+    }
+
+    #[test]
+    fn lexemes_cover_the_source() {
+        for src in CORPUS {
+            let (lexemes, errors) = lex_with_errors(src);
+            let mut spans: Vec<_> = lexemes
+                .iter()
+                .map(|lexeme| (lexeme.start, lexeme.end))
+                .chain(errors.iter().map(|error| (error.start, error.end)))
+                .collect();
+            spans.sort();
+
+            let mut offset = 0;
+            for (start, end) in spans {
+                assert_eq!(start, offset);
+                offset = end;
+            }
+            assert_eq!(offset, src.len());
+        }
+    }
+
+    #[test]
+    fn tokens_reproduce_the_source() {
+        for src in CORPUS {
+            if !lex_with_errors(src).1.is_empty() {
+                continue;
+            }
+
+            let text: String = lex_with_trivia(src)
+                .iter()
+                .map(|token| token.to_string())
+                .collect();
+            assert_eq!(text, src);
+        }
+    }
+
+    #[test]
+    fn coords_count_chars() {
+        for src in CORPUS {
+            for lexeme in lex(src) {
+                let before = &src[..lexeme.start];
+                let line = before.matches('\n').count() + 1;
+                let col = before.rsplit('\n').next().unwrap().chars().count() + 1;
+                assert_eq!(lexeme.start_coord, Coord { line, col });
+            }
+        }
+    }
+
+    #[test]
+    fn lex_non_ascii_string() {
+        let src = "\"crème brûlée\" // → done";
+        let lexemes = lex(src);
+
+        assert_eq!(lexemes.len(), 3);
+        assert_eq!(lexemes[0].kind.unambiguous_unchecked(), LexemeKind::LitStr);
+        assert_eq!(lexemes[0].slice, "\"crème brûlée\"");
+        assert_eq!((lexemes[0].start, lexemes[0].end), (0, 17));
+        assert_eq!(lexemes[0].end_coord, Coord { line: 1, col: 15 });
+        assert_eq!(
+            lexemes[2].kind.unambiguous_unchecked(),
+            LexemeKind::CommentLine
+        );
+        assert_eq!(lexemes[2].slice, "// → done");
+        assert_eq!(lexemes[2].start_coord, Coord { line: 1, col: 16 });
+        assert_eq!(lexemes[2].end, src.len());
+    }
+}